@@ -3,7 +3,7 @@
 // https://mikhailmansion.art
 
 use nannou::prelude::*;
-use nannou_touchosc::TouchOscClient;
+use nannou_touchosc::{TouchOscClient, TouchOscRadioEnum};
 
 fn main() {
     nannou::app(model).update(update).run();
@@ -13,6 +13,17 @@ struct Model {
     touchosc: TouchOscClient,
 }
 
+// EXAMPLE: Mapping a radio input to an enum.
+// Each variant corresponds to one radio segment, in order.
+#[derive(Clone, Copy)]
+enum Invert {
+    Off,
+    On,
+}
+impl TouchOscRadioEnum for Invert {
+    const VARIANTS: &'static [Self] = &[Invert::Off, Invert::On];
+}
+
 fn model(app: &App) -> Model {
     app.new_window().size(600, 600).view(view).build().unwrap();
 
@@ -28,7 +39,7 @@ fn model(app: &App) -> Model {
     // Inputs are initialized by calling their respective add_ method, and passing initialization values.
     // See the README documentaiton for a breakdown of the init values used for each type of TouchOSC controller.
     touchosc.add_button("/show_points", true);
-    touchosc.add_radio("/invert", 2, 0);
+    touchosc.add_radio_enum::<Invert>("/invert");
     touchosc.add_grid("/grid", 2, 3.0, 24.0, 10.0);
     touchosc.add_encoder("/rotate", 0.0, PI * 2.0, 0.0);
     touchosc.add_radial("/offset", 0.0, 10.0, 0.0);
@@ -36,7 +47,7 @@ fn model(app: &App) -> Model {
     touchosc.add_fader("/color_g", 0.0, 1.0, 0.0);
     touchosc.add_fader("/color_b", 0.0, 1.0, 1.0);
     touchosc.add_fader("/color_a", 0.0, 1.0, 1.0);
-    touchosc.add_xy("/scale", 0.1, 3.0, 1.0);
+    touchosc.add_xy_axes("/scale", (0.1, 3.0, 1.0), (0.1, 2.0, 1.0));
     touchosc.add_fader("/stroke_width", 1.0, 10.0, 2.0);
     touchosc.add_fader("/vertices", 3.0, 8.0, 3.0);
     touchosc.add_radar("/scale_rotate", (0.1, 10.0, 1.0), (0.0, PI * 2.0, PI / 4.0));
@@ -57,32 +68,34 @@ fn view(app: &App, m: &Model, frame: Frame) {
 
     // EXAMPLE: Accessing client inputs.
     // To read values from client inputs, call the respective method and pass the registered address.
-    // The most recent stored value will be returned back
+    // The most recent stored value will be returned back.
+    // Reading from a snapshot keeps all values consistent for the whole frame.
+    let touchosc = m.touchosc.snapshot();
 
-    //example: for "radio" inputs, an i32 is returned reprenting a radio index (0-n)
-    let invert = m.touchosc.radio("/invert");
+    //example: "radio" inputs registered with add_radio_enum return the enum variant
+    //(plain add_radio inputs return an i32 radio index (0-n) via radio())
+    let invert = touchosc.radio_enum::<Invert>("/invert");
 
     draw.background().color(match invert {
-        0 => WHITE,
-        1 => BLACK,
-        _ => WHITE,
+        Invert::Off => WHITE,
+        Invert::On => BLACK,
     });
 
     let win_w = app.window_rect().w();
     let win_h = app.window_rect().h();
 
     // example: for "grid" inputs, a fader is accessed via its address followed by a position number (1-n)
-    let rows = m.touchosc.grid("/grid/1").ceil();
-    let cols = m.touchosc.grid("/grid/2").ceil();
+    let rows = touchosc.grid("/grid/1").ceil();
+    let cols = touchosc.grid("/grid/2").ceil();
 
     // example: "fader" inputs return f32
-    let stroke_width = m.touchosc.fader("/stroke_width");
+    let stroke_width = touchosc.fader("/stroke_width");
 
     let grid_margin = map_range(stroke_width, 1.0, 10.0, 100.0, 0.0);
 
     // example: "encoder" inputs work like faders, returning f32
-    let grid_rotate = m.touchosc.encoder("/rotate");
-    let grid_points = m.touchosc.button("/show_points");
+    let grid_rotate = touchosc.encoder("/rotate");
+    let grid_points = touchosc.button("/show_points");
 
     let x_space = (win_w - grid_margin) / cols;
     let y_space = (win_h - grid_margin) / rows;
@@ -90,28 +103,28 @@ fn view(app: &App, m: &Model, frame: Frame) {
     let y_off = -win_h / 2.0 + grid_margin / 2.0;
 
     //example: for "xy" inputs, a vec2 is returned
-    let x_scale = m.touchosc.xy("/scale").x;
-    let y_scale = m.touchosc.xy("/scale").y;
+    let x_scale = touchosc.xy("/scale").x;
+    let y_scale = touchosc.xy("/scale").y;
 
-    //example: "radar" inputs work like "xy", returning vec2
-    let scale = m.touchosc.radar("/scale_rotate").x;
-    let rotate = m.touchosc.radar("/scale_rotate").y;
+    //example: "radar" inputs return polar coordinates, the angle in radians
+    let scale_rotate = touchosc.polar("/scale_rotate");
+    let scale = scale_rotate.radius;
+    let rotate = scale_rotate.angle;
 
     // example: "radial" inputs work like faders, returning f32
-    let offset = m.touchosc.radial("/offset");
+    let offset = touchosc.radial("/offset");
 
-    let vertices = m.touchosc.fader("/vertices").round() as usize;
+    let vertices = touchosc.fader("/vertices").round() as usize;
 
     let stroke_color = match invert {
-        0 => BLACK,
-        1 => WHITE,
-        _ => BLACK,
+        Invert::Off => BLACK,
+        Invert::On => WHITE,
     };
     let fill_color = rgba(
-        m.touchosc.fader("/color_r"),
-        m.touchosc.fader("/color_g"),
-        m.touchosc.fader("/color_b"),
-        m.touchosc.fader("/color_a"),
+        touchosc.fader("/color_r"),
+        touchosc.fader("/color_g"),
+        touchosc.fader("/color_b"),
+        touchosc.fader("/color_a"),
     );
 
     // let draw = draw.rotate(PI/3.0);
@@ -0,0 +1,160 @@
+// nannou_touchosc monitor
+// mikhail mansion
+// https://mikhailmansion.art
+
+// Binds an OSC port and prints a live table of incoming messages, one row
+// per address, with argument types, last value, rate, source and a touch
+// indicator (from "<addr>/touch" messages, "<addr>/z" on TouchOSC Mk1). Addresses can be labeled with the
+// control names of a .tosc layout.
+//
+//     cargo run --bin touchosc-monitor -- 6555 example/touchosc-controller.tosc
+
+use nannou_osc as osc;
+use nannou_touchosc::tosc::TouchOscLayout;
+use nannou_touchosc::{is_touch_addr, osc_type_tags};
+use std::collections::BTreeMap;
+use std::env;
+use std::net::SocketAddr;
+use std::process;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const REFRESH: Duration = Duration::from_millis(100);
+const RATE_WINDOW: Duration = Duration::from_secs(1);
+
+struct Row {
+    types: String,
+    value: String,
+    source: SocketAddr,
+    touched: bool,
+    count: usize, // messages in the current rate window
+    rate: f32,    // messages per second over the last window
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let port = match args.first().map(|port| port.parse::<u16>()) {
+        Some(Ok(port)) => port,
+        _ => {
+            eprintln!("usage: touchosc-monitor <port> [layout.tosc]");
+            process::exit(2);
+        }
+    };
+    let layout = args.get(1).map(|path| match TouchOscLayout::open(path) {
+        Ok(layout) => layout,
+        Err(err) => {
+            eprintln!("cannot read \"{}\": {}", path, err);
+            process::exit(2);
+        }
+    });
+    let receiver = match osc::receiver(port) {
+        Ok(receiver) => receiver,
+        Err(err) => {
+            eprintln!("cannot bind port {}: {}", port, err);
+            process::exit(2);
+        }
+    };
+
+    let mut rows: BTreeMap<String, Row> = BTreeMap::new();
+    let mut window = Instant::now();
+    loop {
+        for (packet, ip_addr) in receiver.try_iter() {
+            for msg in packet.into_msgs() {
+                let args = msg.args.unwrap_or_default();
+                //touch messages only flag their parent control, never get a row
+                if is_touch_addr(&msg.addr) {
+                    let parent = msg.addr.rsplit_once('/').map(|(parent, _)| parent);
+                    if let Some(row) = parent.and_then(|parent| rows.get_mut(parent)) {
+                        row.touched = args.iter().any(is_set);
+                    }
+                    continue;
+                }
+                let row = rows.entry(msg.addr).or_insert(Row {
+                    types: String::new(),
+                    value: String::new(),
+                    source: ip_addr,
+                    touched: false,
+                    count: 0,
+                    rate: 0.0,
+                });
+                row.types = osc_type_tags(&args);
+                row.value = args.iter().map(format_arg).collect::<Vec<_>>().join(" ");
+                row.source = ip_addr;
+                row.count += 1;
+            }
+        }
+
+        let elapsed = window.elapsed();
+        if elapsed >= RATE_WINDOW {
+            for row in rows.values_mut() {
+                row.rate = row.count as f32 / elapsed.as_secs_f32();
+                row.count = 0;
+            }
+            window = Instant::now();
+        }
+
+        print_table(port, &rows, layout.as_ref());
+        thread::sleep(REFRESH);
+    }
+}
+
+fn print_table(port: u16, rows: &BTreeMap<String, Row>, layout: Option<&TouchOscLayout>) {
+    //clear screen, cursor home
+    print!("\x1b[2J\x1b[H");
+    println!("listening on port {} ({} addresses)\n", port, rows.len());
+    println!(
+        "{:<28} {:<18} {:<6} {:<24} {:>7} {:<21} touch",
+        "address", "label", "types", "value", "rate/s", "source"
+    );
+    for (addr, row) in rows {
+        println!(
+            "{:<28} {:<18} {:<6} {:<24} {:>7.1} {:<21} {}",
+            addr,
+            label(addr, layout),
+            row.types,
+            row.value,
+            row.rate,
+            row.source,
+            if row.touched { "*" } else { "" }
+        );
+    }
+}
+
+// layout control name for an address, grid elements are labeled by their grid
+fn label(addr: &str, layout: Option<&TouchOscLayout>) -> String {
+    let layout = match layout {
+        Some(layout) => layout,
+        None => return String::new(),
+    };
+    let control = layout.control(addr).or_else(|| {
+        addr.rsplit_once('/')
+            .and_then(|(base, _)| layout.control(base))
+    });
+    match control {
+        Some(control) => format!("{} ({})", control.name, control.node_type.to_lowercase()),
+        None => String::new(),
+    }
+}
+
+fn format_arg(arg: &osc::Type) -> String {
+    match arg {
+        osc::Type::Int(x) => x.to_string(),
+        osc::Type::Float(x) => format!("{:.3}", x),
+        osc::Type::String(x) => format!("{:?}", x),
+        osc::Type::Long(x) => x.to_string(),
+        osc::Type::Double(x) => format!("{:.3}", x),
+        osc::Type::Bool(x) => x.to_string(),
+        osc::Type::Char(x) => x.to_string(),
+        osc::Type::Blob(x) => format!("<{} bytes>", x.len()),
+        other => format!("{:?}", other),
+    }
+}
+
+fn is_set(arg: &osc::Type) -> bool {
+    match arg {
+        osc::Type::Float(x) => *x > 0.0,
+        osc::Type::Int(x) => *x > 0,
+        osc::Type::Bool(x) => *x,
+        _ => false,
+    }
+}
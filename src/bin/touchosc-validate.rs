@@ -0,0 +1,42 @@
+// nannou_touchosc layout validator
+// mikhail mansion
+// https://mikhailmansion.art
+
+// Compares two .tosc layouts, typically the controller used on stage against
+// one written by `TouchOscClient::export_tosc` from the sketch:
+//
+//     cargo run --bin touchosc-validate -- controller.tosc sketch.tosc
+//
+// Exits with a non-zero status if the layouts don't match.
+
+use nannou_touchosc::tosc::TouchOscLayout;
+use nannou_touchosc::validate::TouchOscValidation;
+use std::env;
+use std::process;
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.len() != 2 {
+        eprintln!("usage: touchosc-validate <controller.tosc> <sketch.tosc>");
+        process::exit(2);
+    }
+
+    let layout = open(&args[0]);
+    let expected = open(&args[1]);
+
+    let report = TouchOscValidation::compare(&expected, &layout);
+    print!("{}", report);
+    if !report.is_ok() {
+        process::exit(1);
+    }
+}
+
+fn open(path: &str) -> TouchOscLayout {
+    match TouchOscLayout::open(path) {
+        Ok(layout) => layout,
+        Err(err) => {
+            eprintln!("cannot read \"{}\": {}", path, err);
+            process::exit(2);
+        }
+    }
+}
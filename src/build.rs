@@ -0,0 +1,320 @@
+// nannou_touchosc build script helper
+// mikhail mansion
+// https://mikhailmansion.art
+
+// Generates a typed parameter struct from a .tosc layout, so renaming a
+// control in the TouchOSC editor breaks compilation instead of silently
+// reading a default at runtime.
+//
+// inside `build.rs`:
+//
+//     fn main() {
+//         nannou_touchosc::build::generate("controller.tosc").unwrap();
+//     }
+//
+// inside the sketch:
+//
+//     include!(concat!(env!("OUT_DIR"), "/touchosc_params.rs"));
+//
+//     TouchOscParams::register(&mut touchosc);
+//     let params = TouchOscParams::read(&touchosc);
+
+use crate::tosc::{invalid_data, TouchOscLayout, TouchOscLayoutControl};
+use crate::TouchOscInputType;
+use std::collections::HashSet;
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub const STRUCT_NAME: &str = "TouchOscParams";
+pub const FILE_NAME: &str = "touchosc_params.rs";
+
+// parses the layout at `path` and writes `$OUT_DIR/touchosc_params.rs`
+pub fn generate<P: AsRef<Path>>(path: P) -> io::Result<PathBuf> {
+    let path = path.as_ref();
+    println!("cargo:rerun-if-changed={}", path.display());
+
+    let layout = TouchOscLayout::open(path)?;
+    let source = generate_source(&layout, STRUCT_NAME)?;
+
+    let out_dir = env::var_os("OUT_DIR")
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "OUT_DIR is not set"))?;
+    let out_file = Path::new(&out_dir).join(FILE_NAME);
+    fs::write(&out_file, source)?;
+    Ok(out_file)
+}
+
+// returns the rust source of a struct with one field per layout control
+pub fn generate_source(layout: &TouchOscLayout, struct_name: &str) -> io::Result<String> {
+    let controls: Vec<&TouchOscLayoutControl> = layout
+        .controls()
+        .iter()
+        .filter(|control| control.input_type.is_some())
+        .collect();
+
+    let mut fields = HashSet::new();
+    for control in &controls {
+        if !fields.insert(field_name(&control.name)) {
+            return Err(invalid_data(format!(
+                "duplicate control name \"{}\" in layout",
+                control.name
+            )));
+        }
+    }
+
+    let mut src = String::new();
+    let _ = writeln!(src, "// generated by nannou_touchosc::build, do not edit");
+    let _ = writeln!(src, "pub struct {} {{", struct_name);
+    for control in &controls {
+        let _ = writeln!(
+            src,
+            "    pub {}: {},",
+            field_name(&control.name),
+            field_type(control)
+        );
+    }
+    let _ = writeln!(src, "}}");
+
+    let _ = writeln!(src, "impl {} {{", struct_name);
+    let _ = writeln!(
+        src,
+        "    pub fn register(client: &mut ::nannou_touchosc::TouchOscClient) {{"
+    );
+    for control in &controls {
+        let _ = writeln!(src, "        {};", register_call(control));
+    }
+    let _ = writeln!(src, "    }}");
+    let _ = writeln!(
+        src,
+        "    pub fn read(client: &::nannou_touchosc::TouchOscClient) -> Self {{"
+    );
+    let _ = writeln!(src, "        {} {{", struct_name);
+    for control in &controls {
+        let _ = writeln!(
+            src,
+            "            {}: {},",
+            field_name(&control.name),
+            read_expr(control)
+        );
+    }
+    let _ = writeln!(src, "        }}");
+    let _ = writeln!(src, "    }}");
+    let _ = writeln!(src, "}}");
+    Ok(src)
+}
+
+// helpers
+
+fn field_name(name: &str) -> String {
+    let mut field: String = name
+        .chars()
+        .map(|c| match c.is_ascii_alphanumeric() {
+            true => c.to_ascii_lowercase(),
+            false => '_',
+        })
+        .collect();
+    if field.is_empty() || field.starts_with(|c: char| c.is_ascii_digit()) {
+        field.insert(0, '_');
+    }
+    if KEYWORDS.contains(&field.as_str()) {
+        field.insert_str(0, "r#");
+    }
+    field
+}
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "static", "struct", "trait", "true", "type", "unsafe", "use", "where",
+    "while", "abstract", "become", "box", "do", "final", "macro", "override", "priv", "try",
+    "typeof", "unsized", "virtual", "yield",
+];
+
+fn field_type(control: &TouchOscLayoutControl) -> String {
+    match control.input_type {
+        Some(TouchOscInputType::Button) => "bool".to_string(),
+        Some(TouchOscInputType::Grid) => format!("[f32; {}]", control.size),
+        Some(TouchOscInputType::Radio) => "i32".to_string(),
+        Some(TouchOscInputType::Text) => "String".to_string(),
+        Some(TouchOscInputType::Radar) | Some(TouchOscInputType::XY) => {
+            "::nannou_touchosc::Vec2".to_string()
+        }
+        _ => "f32".to_string(),
+    }
+}
+
+fn register_call(control: &TouchOscLayoutControl) -> String {
+    let (addr, x, y) = (&control.addr, control.default_x, control.default_y);
+    match control.input_type {
+        Some(TouchOscInputType::Button) => format!("client.add_button({:?}, {})", addr, x > 0.0),
+        Some(TouchOscInputType::Fader) => {
+            format!("client.add_fader({:?}, 0.0, 1.0, {:?})", addr, x)
+        }
+        Some(TouchOscInputType::Grid) => format!(
+            "client.add_grid({:?}, {}, 0.0, 1.0, {:?})",
+            addr, control.size, x
+        ),
+        Some(TouchOscInputType::Encoder) => {
+            format!("client.add_encoder({:?}, 0.0, 1.0, {:?})", addr, x)
+        }
+        Some(TouchOscInputType::Radar) => format!(
+            "client.add_radar({:?}, (0.0, 1.0, {:?}), (0.0, 1.0, {:?}))",
+            addr, x, y
+        ),
+        Some(TouchOscInputType::Radial) => {
+            format!("client.add_radial({:?}, 0.0, 1.0, {:?})", addr, x)
+        }
+        Some(TouchOscInputType::Radio) => format!(
+            "client.add_radio({:?}, {}, {})",
+            addr, control.size, x as i32
+        ),
+        Some(TouchOscInputType::XY) => format!(
+            "client.add_xy_axes({:?}, (0.0, 1.0, {:?}), (0.0, 1.0, {:?}))",
+            addr, x, y
+        ),
+        Some(TouchOscInputType::Text) => format!("client.add_text({:?}, \"\")", addr),
+        None => String::new(),
+    }
+}
+
+fn read_expr(control: &TouchOscLayoutControl) -> String {
+    let addr = &control.addr;
+    match control.input_type {
+        Some(TouchOscInputType::Button) => format!("client.button({:?})", addr),
+        Some(TouchOscInputType::Fader) => format!("client.fader({:?})", addr),
+        Some(TouchOscInputType::Grid) => {
+            let elements: Vec<String> = (1..=control.size)
+                .map(|i| format!("client.grid(\"{}/{}\")", addr.escape_debug(), i))
+                .collect();
+            format!("[{}]", elements.join(", "))
+        }
+        Some(TouchOscInputType::Encoder) => format!("client.encoder({:?})", addr),
+        Some(TouchOscInputType::Radar) => format!("client.radar({:?})", addr),
+        Some(TouchOscInputType::Radial) => format!("client.radial({:?})", addr),
+        Some(TouchOscInputType::Radio) => format!("client.radio({:?})", addr),
+        Some(TouchOscInputType::XY) => format!("client.xy({:?})", addr),
+        Some(TouchOscInputType::Text) => format!("client.text({:?}).to_string()", addr),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn control(name: &str, node_type: &str, addr: &str) -> TouchOscLayoutControl {
+        TouchOscLayoutControl {
+            name: name.to_string(),
+            node_type: node_type.to_string(),
+            input_type: crate::tosc::input_type_from_node(node_type),
+            output_type: crate::tosc::output_type_from_node(node_type, true),
+            addr: addr.to_string(),
+            size: 1,
+            default_x: 0.0,
+            default_y: 0.0,
+        }
+    }
+
+    #[test]
+    fn field_names() {
+        assert_eq!(field_name("Volume"), "volume");
+        assert_eq!(field_name("color-r 2"), "color_r_2");
+        assert_eq!(field_name("1st"), "_1st");
+        assert_eq!(field_name(""), "_");
+        assert_eq!(field_name("type"), "r#type");
+        assert_eq!(field_name("Loop"), "r#loop");
+    }
+
+    #[test]
+    fn duplicate_names() {
+        let layout = TouchOscLayout::new(vec![
+            control("color r", "FADER", "/a"),
+            control("Color-R", "FADER", "/b"),
+        ]);
+        let err = generate_source(&layout, STRUCT_NAME).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("Color-R"));
+    }
+
+    #[test]
+    fn skips_non_inputs() {
+        let layout = TouchOscLayout::new(vec![
+            control("group", "GROUP", "/group"),
+            control("fader", "FADER", "/fader"),
+        ]);
+        let src = generate_source(&layout, STRUCT_NAME).unwrap();
+        assert!(!src.contains("group"));
+        assert!(src.contains("pub fader: f32,"));
+    }
+
+    #[test]
+    fn every_control_type() {
+        let mut button = control("button", "BUTTON", "/button");
+        button.default_x = 1.0;
+        let mut fader = control("fader", "FADER", "/fader");
+        fader.default_x = 0.25;
+        let mut grid = control("grid", "GRID", "/grid");
+        grid.size = 2;
+        grid.default_x = 0.5;
+        let encoder = control("encoder", "ENCODER", "/encoder");
+        let mut radar = control("radar", "RADAR", "/radar");
+        radar.default_x = 0.5;
+        radar.default_y = 0.75;
+        let radial = control("radial", "RADIAL", "/radial");
+        let mut radio = control("radio", "RADIO", "/radio");
+        radio.size = 4;
+        radio.default_x = 2.0;
+        let mut xy = control("xy", "XY", "/xy");
+        xy.default_x = 0.25;
+        xy.default_y = 0.75;
+        let text = control("text", "TEXT", "/text");
+        let layout = TouchOscLayout::new(vec![
+            button, fader, grid, encoder, radar, radial, radio, xy, text,
+        ]);
+        let src = generate_source(&layout, "Params").unwrap();
+
+        let expected = [
+            //fields
+            "pub struct Params {",
+            "    pub button: bool,",
+            "    pub fader: f32,",
+            "    pub grid: [f32; 2],",
+            "    pub encoder: f32,",
+            "    pub radar: ::nannou_touchosc::Vec2,",
+            "    pub radial: f32,",
+            "    pub radio: i32,",
+            "    pub xy: ::nannou_touchosc::Vec2,",
+            "    pub text: String,",
+            //register
+            "        client.add_button(\"/button\", true);",
+            "        client.add_fader(\"/fader\", 0.0, 1.0, 0.25);",
+            "        client.add_grid(\"/grid\", 2, 0.0, 1.0, 0.5);",
+            "        client.add_encoder(\"/encoder\", 0.0, 1.0, 0.0);",
+            "        client.add_radar(\"/radar\", (0.0, 1.0, 0.5), (0.0, 1.0, 0.75));",
+            "        client.add_radial(\"/radial\", 0.0, 1.0, 0.0);",
+            "        client.add_radio(\"/radio\", 4, 2);",
+            "        client.add_xy_axes(\"/xy\", (0.0, 1.0, 0.25), (0.0, 1.0, 0.75));",
+            "        client.add_text(\"/text\", \"\");",
+            //read
+            "            button: client.button(\"/button\"),",
+            "            fader: client.fader(\"/fader\"),",
+            "            grid: [client.grid(\"/grid/1\"), client.grid(\"/grid/2\")],",
+            "            encoder: client.encoder(\"/encoder\"),",
+            "            radar: client.radar(\"/radar\"),",
+            "            radial: client.radial(\"/radial\"),",
+            "            radio: client.radio(\"/radio\"),",
+            "            xy: client.xy(\"/xy\"),",
+            "            text: client.text(\"/text\").to_string(),",
+        ];
+        for line in expected {
+            assert!(
+                src.lines().any(|l| l == line),
+                "missing {:?} in\n{}",
+                line,
+                src
+            );
+        }
+    }
+}
@@ -24,13 +24,58 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use nannou::prelude::*;
 use nannou_osc as osc;
 use regex::escape;
 use regex::Regex;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+#[cfg(feature = "tosc")]
+use std::io;
+use std::net::{SocketAddr, ToSocketAddrs};
+#[cfg(feature = "tosc")]
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+#[cfg(feature = "tosc")]
+use tosc::{TouchOscLayout, TouchOscLayoutControl};
+use tracing::{debug, debug_span, warn};
+#[cfg(feature = "tosc")]
+use validate::TouchOscValidation;
 
-#[derive(Debug)]
+pub use math::Vec2;
+use math::{map_range, pt2};
+use outgoing::TouchOscOutgoing;
+pub use outgoing::{TouchOscSendStats, MAX_BUNDLE_SIZE};
+pub use pages::TouchOscPageChange;
+use pages::TouchOscPages;
+pub use polar::{Polar, TouchOscAngle};
+use reader::write_lock;
+pub use reader::TouchOscReader;
+pub use scope::TouchOscScope;
+pub use snapshot::TouchOscSnapshot;
+#[cfg(feature = "async")]
+pub use stream::{TouchOscEvent, TouchOscStream};
+use taps::TouchOscTaps;
+pub use taps::DEFAULT_DOUBLE_TAP_INTERVAL;
+
+mod math;
+mod outgoing;
+mod pages;
+mod polar;
+mod reader;
+mod scope;
+mod snapshot;
+#[cfg(feature = "async")]
+mod stream;
+mod taps;
+
+#[cfg(feature = "tosc")]
+pub mod build;
+#[cfg(feature = "tosc")]
+pub mod tosc;
+#[cfg(feature = "tosc")]
+pub mod validate;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum TouchOscInputType {
     Button,
     Fader,
@@ -40,10 +85,18 @@ pub enum TouchOscInputType {
     Radial,
     Radio,
     XY,
+    Text,
+}
+
+// controls the sketch writes and the device displays
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TouchOscOutputType {
+    Label,
+    Meter,
 }
 
 pub struct TouchOscClient {
-    osc_receiver: osc::Receiver,
+    osc_receiver: Option<osc::Receiver>, //None once moved to a receiver thread
 
     //reference
     lookup_table: HashMap<String, TouchOscInputType>,
@@ -57,14 +110,52 @@ pub struct TouchOscClient {
     touchosc_radials: HashMap<String, TouchOscRadial>,
     touchosc_radios: HashMap<String, TouchOscRadio>,
     touchosc_xys: HashMap<String, TouchOscXY>,
+    touchosc_texts: HashMap<String, TouchOscText>,
+
+    //outputs
+    osc_sender: Option<osc::Sender<osc::Connected>>, //None until connect()
+    output_table: HashMap<String, TouchOscOutputType>,
+    touchosc_labels: HashMap<String, TouchOscLabel>,
+    touchosc_meters: HashMap<String, TouchOscMeter>,
+    outgoing: TouchOscOutgoing,
+
+    //unregistered addresses and osc learn
+    unknown_addrs: HashMap<String, TouchOscUnknownAddr>,
+    pending_learns: Vec<TouchOscLearn>,
+    learned_routes: HashMap<String, String>, //incoming addr -> registered addr
+
+    type_errors: HashMap<String, TouchOscTypeError>,
+
+    generation: u64, //bumped on every update()
+
+    //per update() message handling
+    coalesce: bool,
+    keep_history: bool,
+    history: HashMap<String, Vec<TouchOscValue>>, //values applied in the last update()
+    collapsed: HashMap<String, usize>,            //messages collapsed in the last update()
+    collapsed_total: u64,
+
+    pages: TouchOscPages,
+    taps: TouchOscTaps,
 
     verbose: bool,
+    strict: bool,
+    feedback_on_edit: bool,
 }
 
 impl TouchOscClient {
     pub fn new(port: u16) -> Self {
+        let receiver = osc::receiver(port).unwrap(); //Bind `osc::Receiver` to port.
+        TouchOscClient {
+            osc_receiver: Some(receiver),
+            ..Self::unbound()
+        }
+    }
+    // a client without an OSC receiver, for messages received elsewhere
+    // (e.g. the async stream)
+    pub fn unbound() -> Self {
         TouchOscClient {
-            osc_receiver: osc::receiver(port).unwrap(), //Bind `osc::Receiver` to port.
+            osc_receiver: None,
             lookup_table: HashMap::new(),
             touchosc_buttons: HashMap::new(),
             touchosc_grids: HashMap::new(),
@@ -74,148 +165,484 @@ impl TouchOscClient {
             touchosc_radials: HashMap::new(),
             touchosc_radios: HashMap::new(),
             touchosc_xys: HashMap::new(),
+            touchosc_texts: HashMap::new(),
+            osc_sender: None,
+            output_table: HashMap::new(),
+            touchosc_labels: HashMap::new(),
+            touchosc_meters: HashMap::new(),
+            outgoing: TouchOscOutgoing::default(),
+            unknown_addrs: HashMap::new(),
+            pending_learns: Vec::new(),
+            learned_routes: HashMap::new(),
+            type_errors: HashMap::new(),
+            generation: 0,
+            coalesce: false,
+            keep_history: false,
+            history: HashMap::new(),
+            collapsed: HashMap::new(),
+            collapsed_total: 0,
+            pages: TouchOscPages::default(),
+            taps: TouchOscTaps::default(),
             verbose: false,
+            strict: false,
+            feedback_on_edit: false,
         }
     }
     pub fn verbose(&mut self) {
-        self.verbose = true; //prints all received messages
+        self.verbose = true; //logs all received messages (debug level)
+    }
+    pub fn strict(&mut self) {
+        self.strict = true; //no argument coercion, only native TouchOSC types
+    }
+    pub fn feedback_on_edit(&mut self) {
+        self.feedback_on_edit = true; //range, curve and default edits resend the input to the device
+    }
+    pub fn coalesce(&mut self) {
+        self.coalesce = true; //applies only the latest message per address in each update()
+    }
+    pub fn keep_history(&mut self) {
+        self.keep_history = true; //records every value applied in each update(), see history()
     }
     pub fn update(&mut self) {
-        for (packet, ip_addr) in self.osc_receiver.try_iter() {
+        let packets: Vec<_> = match &self.osc_receiver {
+            Some(receiver) => receiver.try_iter().collect(),
+            None => return,
+        };
+        self.generation += 1;
+        self.history.clear();
+        self.collapsed.clear();
+
+        let mut messages = Vec::new();
+        for (packet, ip_addr) in packets {
+            for msg in packet.into_msgs() {
+                messages.push((msg, ip_addr));
+            }
+        }
+        if self.coalesce {
+            messages = self.coalesce_messages(messages);
+        }
+        for (msg, ip_addr) in messages {
+            let _source =
+                debug_span!(target: "nannou_touchosc::dispatch", "message", source = %ip_addr)
+                    .entered();
+            let input_addr = match self.apply_message(msg) {
+                Some(input_addr) => input_addr,
+                None => continue,
+            };
+            if self.keep_history {
+                if let Some(value) = self.value(&input_addr) {
+                    self.history.entry(input_addr).or_default().push(value);
+                }
+            }
+        }
+        self.send_meters();
+        self.flush();
+    }
+    // moves the OSC receiver to its own thread, which applies messages as they
+    // arrive instead of once per update(). Values are read through the returned
+    // handle, which can be cloned and shared with other threads.
+    // The thread exits on the first message after every handle is dropped.
+    pub fn spawn_receiver(mut self) -> TouchOscReader {
+        let receiver = self.osc_receiver.take();
+        let reader = TouchOscReader::new(self);
+        if let Some(receiver) = receiver {
+            let client = reader.downgrade();
+            thread::spawn(move || {
+                while let Ok((packet, ip_addr)) = receiver.recv() {
+                    match client.upgrade() {
+                        Some(client) => {
+                            let mut client = write_lock(&client);
+                            client.generation += 1;
+                            client.apply_packet(packet, ip_addr);
+                        }
+                        None => break,
+                    }
+                }
+            });
+        }
+        return reader;
+    }
+    // applies every message in the packet,
+    // returns the addresses of the inputs that received a message
+    fn apply_packet(&mut self, packet: osc::Packet, ip_addr: SocketAddr) -> Vec<String> {
+        let _source =
+            debug_span!(target: "nannou_touchosc::dispatch", "packet", source = %ip_addr).entered();
+        let mut applied = Vec::new();
+        for msg in packet.into_msgs() {
+            applied.extend(self.apply_message(msg));
+        }
+        return applied;
+    }
+    // returns the address of the input that received the message
+    fn apply_message(&mut self, msg: osc::Message) -> Option<String> {
+        let args = msg.args.unwrap_or_default();
+        if self.pages.is_pager(&msg.addr) {
+            self.apply_pager(&msg.addr, &args);
+            return None;
+        }
+        let addr = match self.learned_routes.get(&msg.addr) {
+            Some(addr) => addr.clone(),
+            None => msg.addr.clone(),
+        };
+        if !self.pages.accepts(&addr) {
             if self.verbose {
-                println!("from: {}", ip_addr);
+                debug!(target: "nannou_touchosc::pages", addr = %addr, "ignored, page disabled");
             }
-            for msg in packet.into_msgs() {
-                let args = msg.args.unwrap();
-                let mut found_key = false; //TODO: remove this
-                for (key, input_type) in &self.lookup_table {
-                    if key == &msg.addr {
-                        //exact addr match
-                        match &input_type {
-                            TouchOscInputType::Button => {
-                                match self.touchosc_buttons.get_mut(&msg.addr) {
-                                    Some(button) => {
-                                        button.set_state(match &args[..] {
-                                            [osc::Type::Float(x)] => *x,
-                                            _etc => button.value(),
-                                        });
-                                        button.print(&msg.addr, self.verbose);
-                                        found_key = true;
-                                    }
-                                    None => (),
-                                }
+            return None;
+        }
+        self.track_touch(&addr, &args);
+        if let Some((target, down)) = self.touch_of(&addr, &args) {
+            return self.apply_touch(&target, down);
+        }
+        if self.taps.holds(&addr) {
+            return None;
+        }
+        let applied = match self.dispatch(&addr, &args) {
+            Some(input_addr) => Some(input_addr),
+            None => {
+                if self.bind_learn(&msg.addr, &args) {
+                    let addr = self.learned_routes[&msg.addr].clone();
+                    self.dispatch(&addr, &args)
+                } else {
+                    self.capture_unknown(&msg.addr, args);
+                    None
+                }
+            }
+        };
+        if let Some(input_addr) = &applied {
+            //the device now shows the incoming value, not the last one sent
+            self.forget_sent_addr(&msg.addr);
+            self.forget_sent_addr(&addr);
+            self.forget_sent_addr(input_addr);
+        }
+        return applied;
+    }
+    // keeps the latest message per address of a continuous input, counting the
+    // collapsed ones. Presses, touches and page changes are all applied.
+    fn coalesce_messages(
+        &mut self,
+        messages: Vec<(osc::Message, SocketAddr)>,
+    ) -> Vec<(osc::Message, SocketAddr)> {
+        let mut latest: Vec<(osc::Message, SocketAddr)> = Vec::new();
+        let mut seen = HashSet::new();
+        for (msg, ip_addr) in messages.into_iter().rev() {
+            if self.is_continuous(&msg.addr) && !seen.insert(msg.addr.clone()) {
+                *self.collapsed.entry(msg.addr).or_insert(0) += 1;
+                self.collapsed_total += 1;
+            } else {
+                latest.push((msg, ip_addr));
+            }
+        }
+        latest.reverse();
+        return latest;
+    }
+
+    // whether a message to addr (as received) sets a fader, encoder, radial,
+    // xy or radar, including split "/xy/x" and "/radar/y" messages
+    fn is_continuous(&self, addr: &str) -> bool {
+        if self.pages.is_pager(addr) {
+            return false;
+        }
+        let addr = match self.learned_routes.get(addr) {
+            Some(addr) => addr.as_str(),
+            None => addr,
+        };
+        let input_type = match self.lookup_table.get(addr) {
+            Some(input_type) => Some(input_type),
+            None => match addr.rsplit_once('/') {
+                Some((base, axis)) if axis == "x" || axis == "y" => {
+                    self.lookup_table.get(base).filter(|input_type| {
+                        matches!(input_type, TouchOscInputType::XY | TouchOscInputType::Radar)
+                    })
+                }
+                _ => None,
+            },
+        };
+        return matches!(
+            input_type,
+            Some(
+                TouchOscInputType::Fader
+                    | TouchOscInputType::Encoder
+                    | TouchOscInputType::Radial
+                    | TouchOscInputType::XY
+                    | TouchOscInputType::Radar
+            )
+        );
+    }
+    // touch-down starts a new drag on a radar, see TouchOscRadar::start_drag
+    fn track_touch(&mut self, addr: &str, args: &[osc::Type]) {
+        if !is_touch_addr(addr) || self.lookup_table.contains_key(addr) {
+            return;
+        }
+        let radar = match addr.rsplit_once('/') {
+            Some((base, _)) => self.touchosc_radars.get_mut(base),
+            None => None,
+        };
+        if let Some(radar) = radar {
+            if float_arg(args, false).is_some_and(|x| x > 0.0) {
+                radar.start_drag();
+            }
+        }
+    }
+    // routes a message to the input registered at addr, returns the address of
+    // the input it was applied to (xy base for split messages), None if not registered
+    fn dispatch(&mut self, addr: &str, args: &[osc::Type]) -> Option<String> {
+        let strict = self.strict;
+        let generation = self.generation;
+        let mut found_key = false; //TODO: remove this
+        let mut input_addr = addr;
+        let mut type_error = None;
+        for (key, input_type) in &self.lookup_table {
+            if key == addr {
+                //exact addr match
+                match &input_type {
+                    TouchOscInputType::Button => match self.touchosc_buttons.get_mut(addr) {
+                        Some(button) => {
+                            let before = button.state();
+                            match float_arg(args, strict) {
+                                Some(x) => button.set_state(x),
+                                None => type_error = Some(*input_type),
                             }
-                            TouchOscInputType::Fader => {
-                                match self.touchosc_faders.get_mut(&msg.addr) {
-                                    Some(fader) => {
-                                        fader.set_value(match &args[..] {
-                                            [osc::Type::Float(x)] => *x,
-                                            _etc => fader.value(),
-                                        });
-                                        fader.print(&msg.addr, self.verbose);
-                                        found_key = true;
-                                    }
-                                    None => (),
-                                }
+                            if button.state() != before {
+                                button.mark(generation);
                             }
-                            TouchOscInputType::Encoder => {
-                                match self.touchosc_encoders.get_mut(&msg.addr) {
-                                    Some(encoder) => {
-                                        encoder.set_value(match &args[..] {
-                                            [osc::Type::Float(x)] => *x,
-                                            _etc => encoder.value(),
-                                        });
-                                        encoder.print(&msg.addr, self.verbose);
-                                        found_key = true;
-                                    }
-                                    None => (),
-                                }
+                            button.print(addr, self.verbose);
+                            found_key = true;
+                        }
+                        None => (),
+                    },
+                    TouchOscInputType::Fader => match self.touchosc_faders.get_mut(addr) {
+                        Some(fader) => {
+                            let before = fader.value();
+                            match float_arg(args, strict) {
+                                Some(x) => fader.set_value(x),
+                                None => type_error = Some(*input_type),
                             }
-                            TouchOscInputType::Radar => {
-                                match self.touchosc_radars.get_mut(&msg.addr) {
-                                    Some(radar) => {
-                                        radar.set_values(match &args[..] {
-                                            [osc::Type::Float(x), osc::Type::Float(y)] => {
-                                                pt2(*x, *y)
-                                            }
-                                            _etc => radar.values(),
-                                        });
-                                        radar.print(&msg.addr, self.verbose);
-                                        found_key = true;
-                                    }
-                                    None => (),
-                                }
+                            if fader.value() != before {
+                                fader.mark(generation);
                             }
-                            TouchOscInputType::Radial => {
-                                match self.touchosc_radials.get_mut(&msg.addr) {
-                                    Some(radial) => {
-                                        radial.set_value(match &args[..] {
-                                            [osc::Type::Float(x)] => *x,
-                                            _etc => radial.value(),
-                                        });
-                                        radial.print(&msg.addr, self.verbose);
-                                        found_key = true;
-                                    }
-                                    None => (),
-                                }
+                            fader.print(addr, self.verbose);
+                            found_key = true;
+                        }
+                        None => (),
+                    },
+                    TouchOscInputType::Encoder => match self.touchosc_encoders.get_mut(addr) {
+                        Some(encoder) => {
+                            let before = encoder.value();
+                            match float_arg(args, strict) {
+                                Some(x) => encoder.set_value(x),
+                                None => type_error = Some(*input_type),
+                            }
+                            if encoder.value() != before {
+                                encoder.mark(generation);
+                            }
+                            encoder.print(addr, self.verbose);
+                            found_key = true;
+                        }
+                        None => (),
+                    },
+                    TouchOscInputType::Radar => match self.touchosc_radars.get_mut(addr) {
+                        Some(radar) => {
+                            let before = radar.values();
+                            match vec2_arg(args, strict) {
+                                Some(values) => radar.set_values(values),
+                                None => type_error = Some(*input_type),
+                            }
+                            if radar.values() != before {
+                                radar.mark(generation);
+                            }
+                            radar.print(addr, self.verbose);
+                            found_key = true;
+                        }
+                        None => (),
+                    },
+                    TouchOscInputType::Radial => match self.touchosc_radials.get_mut(addr) {
+                        Some(radial) => {
+                            let before = radial.value();
+                            match float_arg(args, strict) {
+                                Some(x) => radial.set_value(x),
+                                None => type_error = Some(*input_type),
+                            }
+                            if radial.value() != before {
+                                radial.mark(generation);
                             }
-                            TouchOscInputType::Radio => {
-                                match self.touchosc_radios.get_mut(&msg.addr) {
-                                    Some(radio) => {
-                                        radio.set_value(match &args[..] {
-                                            [osc::Type::Int(x)] => *x,
-                                            _etc => radio.value(),
-                                        });
-                                        radio.print(&msg.addr, self.verbose);
-                                        found_key = true;
+                            radial.print(addr, self.verbose);
+                            found_key = true;
+                        }
+                        None => (),
+                    },
+                    TouchOscInputType::Radio => match self.touchosc_radios.get_mut(addr) {
+                        Some(radio) => {
+                            let before = radio.value();
+                            match int_arg(args, strict) {
+                                Some(x) => radio.set_value(x),
+                                None => type_error = Some(*input_type),
+                            }
+                            if radio.value() != before {
+                                radio.mark(generation);
+                            }
+                            radio.print(addr, self.verbose);
+                            found_key = true;
+                        }
+                        None => (),
+                    },
+                    TouchOscInputType::XY => match self.touchosc_xys.get_mut(addr) {
+                        Some(xy) => {
+                            let before = xy.values();
+                            match vec2_arg(args, strict) {
+                                Some(values) => xy.set_values(values),
+                                None => type_error = Some(*input_type),
+                            }
+                            if xy.values() != before {
+                                xy.mark(generation);
+                            }
+                            xy.print(addr, self.verbose);
+                            found_key = true;
+                        }
+                        None => (),
+                    },
+                    TouchOscInputType::Text => match self.touchosc_texts.get_mut(addr) {
+                        Some(text) => {
+                            let before = text.value().to_string();
+                            match text_arg(args, strict) {
+                                Some(value) => text.set_value(&value),
+                                None => type_error = Some(*input_type),
+                            }
+                            if text.value() != before {
+                                text.mark(generation);
+                            }
+                            text.print(addr, self.verbose);
+                            found_key = true;
+                        }
+                        None => (),
+                    },
+                    _ => {
+                        warn!(target: "nannou_touchosc::dispatch", addr, "input not found");
+                    }
+                };
+            } else if Regex::new(format!(r#"{}/\d+"#, escape(key)).as_str())
+                .unwrap()
+                .is_match(addr)
+                && !found_key
+            {
+                //partial addr match
+                match &input_type {
+                    TouchOscInputType::Grid => match self.touchosc_grids.get_mut(key) {
+                        Some(grid) => {
+                            match float_arg(args, strict) {
+                                Some(x) => {
+                                    let before = grid.faders.get(addr).map(|fader| fader.value());
+                                    grid.set_value(addr, x);
+                                    let mut changed = false;
+                                    if let Some(fader) = grid.faders.get_mut(addr) {
+                                        if Some(fader.value()) != before {
+                                            fader.mark(generation);
+                                            changed = true;
+                                        }
+                                    }
+                                    if changed {
+                                        grid.mark(generation);
                                     }
-                                    None => (),
                                 }
+                                None => type_error = Some(*input_type),
                             }
-                            TouchOscInputType::XY => match self.touchosc_xys.get_mut(&msg.addr) {
-                                Some(xy) => {
-                                    xy.set_values(match &args[..] {
-                                        [osc::Type::Float(x), osc::Type::Float(y)] => pt2(*x, *y),
-                                        _etc => xy.values(),
-                                    });
-                                    xy.print(&msg.addr, self.verbose);
-                                    found_key = true;
-                                }
-                                None => (),
-                            },
-                            _ => {
-                                println!("Input not found")
+                            grid.print(addr, self.verbose);
+                            found_key = true;
+                        }
+                        None => (),
+                    },
+                    _ => {
+                        warn!(target: "nannou_touchosc::dispatch", addr, "input not found");
+                    }
+                }
+            }
+        }
+        if !found_key {
+            //split xy/radar messages, e.g. "/xy/x" and "/xy/y"
+            match addr.rsplit_once('/') {
+                Some((base, axis)) if axis == "x" || axis == "y" => {
+                    match self.lookup_table.get(base) {
+                        Some(TouchOscInputType::XY) => {
+                            let xy = self.touchosc_xys.get_mut(base).unwrap();
+                            let before = xy.values();
+                            match float_arg(args, strict) {
+                                Some(x) if axis == "x" => xy.set_x(x),
+                                Some(y) => xy.set_y(y),
+                                None => type_error = Some(TouchOscInputType::XY),
                             }
-                        };
-                    } else if Regex::new(format!(r#"{}/\d+"#, escape(key)).as_str())
-                        .unwrap()
-                        .is_match(&msg.addr)
-                        && !found_key
-                    {
-                        //partial addr match
-                        match &input_type {
-                            TouchOscInputType::Grid => match self.touchosc_grids.get_mut(key) {
-                                Some(grid) => {
-                                    grid.set_value(
-                                        &msg.addr,
-                                        match &args[..] {
-                                            [osc::Type::Float(x)] => *x,
-                                            _etc => grid.value(&msg.addr),
-                                        },
-                                    );
-                                    grid.print(&msg.addr, self.verbose);
-                                    found_key = true;
-                                }
-                                None => (),
-                            },
-                            _ => {
-                                println!("Input not found")
+                            if xy.values() != before {
+                                xy.mark(generation);
                             }
+                            xy.print(base, self.verbose);
+                            input_addr = base;
+                            found_key = true;
                         }
+                        Some(TouchOscInputType::Radar) => {
+                            let radar = self.touchosc_radars.get_mut(base).unwrap();
+                            let before = radar.values();
+                            match float_arg(args, strict) {
+                                Some(x) if axis == "x" => radar.set_radius(x),
+                                Some(y) => radar.set_rotation(y),
+                                None => type_error = Some(TouchOscInputType::Radar),
+                            }
+                            if radar.values() != before {
+                                radar.mark(generation);
+                            }
+                            radar.print(base, self.verbose);
+                            input_addr = base;
+                            found_key = true;
+                        }
+                        _ => (),
                     }
                 }
+                _ => (),
             }
         }
+        if let Some(input_type) = type_error {
+            self.capture_type_error(addr, input_type, args);
+        }
+        if !found_key {
+            return None;
+        }
+        return Some(input_addr.to_string());
+    }
+    fn capture_type_error(
+        &mut self,
+        addr: &str,
+        input_type: TouchOscInputType,
+        args: &[osc::Type],
+    ) {
+        if self.verbose {
+            debug!(
+                target: "nannou_touchosc::dispatch",
+                addr,
+                types = %osc_type_tags(args),
+                input_type = ?input_type,
+                "cannot read arguments"
+            );
+        }
+        let error = self
+            .type_errors
+            .entry(addr.to_string())
+            .or_insert(TouchOscTypeError {
+                addr: addr.to_string(),
+                input_type,
+                types: String::new(),
+                count: 0,
+            });
+        error.types = osc_type_tags(args);
+        error.count += 1;
+    }
+
+    // argument type errors, sorted by address
+    // (in strict mode only the native TouchOSC types are accepted)
+    pub fn type_errors(&self) -> Vec<&TouchOscTypeError> {
+        let mut errors: Vec<&TouchOscTypeError> = self.type_errors.values().collect();
+        errors.sort_by(|a, b| a.addr.cmp(&b.addr));
+        return errors;
+    }
+    pub fn clear_type_errors(&mut self) {
+        self.type_errors.clear();
     }
 
     // add inputs to client
@@ -273,11 +700,18 @@ impl TouchOscClient {
     }
     pub fn add_radio(&mut self, addr: &str, size: usize, default: i32) {
         self.verify_free_addr(addr);
+        if default < 0 || default as usize >= size {
+            panic!("\"{}\" default index {} out of range!", addr, default);
+        }
         self.lookup_table
             .insert((&addr).to_string(), TouchOscInputType::Radio);
         self.touchosc_radios
             .insert((&addr).to_string(), TouchOscRadio::new(size, default));
     }
+    pub fn add_radio_enum<T: TouchOscRadioEnum>(&mut self, addr: &str) {
+        //radio size is taken from the number of enum variants
+        self.add_radio(addr, T::VARIANTS.len(), 0);
+    }
     pub fn add_xy(&mut self, addr: &str, min: f32, max: f32, default: f32) {
         self.verify_free_addr(addr);
         self.lookup_table
@@ -285,6 +719,551 @@ impl TouchOscClient {
         self.touchosc_xys
             .insert((&addr).to_string(), TouchOscXY::new(min, max, default));
     }
+    // (min, max, default) per axis
+    pub fn add_xy_axes(&mut self, addr: &str, x: (f32, f32, f32), y: (f32, f32, f32)) {
+        self.verify_free_addr(addr);
+        self.lookup_table
+            .insert((&addr).to_string(), TouchOscInputType::XY);
+        self.touchosc_xys
+            .insert((&addr).to_string(), TouchOscXY::with_axes(x, y));
+    }
+    pub fn add_text(&mut self, addr: &str, default: &str) {
+        self.verify_free_addr(addr);
+        self.lookup_table
+            .insert((&addr).to_string(), TouchOscInputType::Text);
+        self.touchosc_texts
+            .insert((&addr).to_string(), TouchOscText::new(default));
+    }
+
+    // remove inputs from client
+
+    // returns the type of the removed input, None if addr was not registered
+    // learned routes, pending learns and type errors for addr are dropped as well
+    pub fn remove(&mut self, addr: &str) -> Option<TouchOscInputType> {
+        let input_type = self.remove_input(addr)?;
+        self.learned_routes
+            .retain(|_, registered| registered != addr);
+        self.type_errors.remove(addr);
+        self.taps.remove(addr);
+        return Some(input_type);
+    }
+    // removes the input at prefix and every input below it ("/scene2" removes
+    // "/scene2/speed" but not "/scene20"), returns the number of removed inputs
+    // outputs below prefix are removed as well
+    pub fn remove_prefix(&mut self, prefix: &str) -> usize {
+        let prefix = prefix.trim_end_matches('/');
+        let addrs: Vec<String> = self
+            .lookup_table
+            .keys()
+            .filter(|addr| in_namespace(addr, prefix))
+            .cloned()
+            .collect();
+        for addr in &addrs {
+            self.remove(addr);
+        }
+        let outputs: Vec<String> = self
+            .output_table
+            .keys()
+            .filter(|addr| in_namespace(addr, prefix))
+            .cloned()
+            .collect();
+        for addr in &outputs {
+            self.remove_output(addr);
+        }
+        return addrs.len() + outputs.len();
+    }
+    // removes every input and output, unknown addresses are kept
+    pub fn clear(&mut self) {
+        self.output_table.clear();
+        self.touchosc_labels.clear();
+        self.touchosc_meters.clear();
+        self.lookup_table.clear();
+        self.touchosc_buttons.clear();
+        self.touchosc_faders.clear();
+        self.touchosc_grids.clear();
+        self.touchosc_encoders.clear();
+        self.touchosc_radars.clear();
+        self.touchosc_radials.clear();
+        self.touchosc_radios.clear();
+        self.touchosc_xys.clear();
+        self.touchosc_texts.clear();
+        self.pending_learns.clear();
+        self.learned_routes.clear();
+        self.type_errors.clear();
+        self.taps.clear();
+    }
+    fn remove_input(&mut self, addr: &str) -> Option<TouchOscInputType> {
+        self.pending_learns.retain(|learn| learn.addr != addr);
+        let input_type = self.lookup_table.remove(addr)?;
+        match input_type {
+            TouchOscInputType::Button => {
+                self.touchosc_buttons.remove(addr);
+            }
+            TouchOscInputType::Fader => {
+                self.touchosc_faders.remove(addr);
+            }
+            TouchOscInputType::Grid => {
+                self.touchosc_grids.remove(addr);
+            }
+            TouchOscInputType::Encoder => {
+                self.touchosc_encoders.remove(addr);
+            }
+            TouchOscInputType::Radar => {
+                self.touchosc_radars.remove(addr);
+            }
+            TouchOscInputType::Radial => {
+                self.touchosc_radials.remove(addr);
+            }
+            TouchOscInputType::Radio => {
+                self.touchosc_radios.remove(addr);
+            }
+            TouchOscInputType::XY => {
+                self.touchosc_xys.remove(addr);
+            }
+            TouchOscInputType::Text => {
+                self.touchosc_texts.remove(addr);
+            }
+        }
+        return Some(input_type);
+    }
+    // returns the type of the removed output, None if addr was not registered
+    pub fn remove_output(&mut self, addr: &str) -> Option<TouchOscOutputType> {
+        let output_type = self.output_table.remove(addr)?;
+        match output_type {
+            TouchOscOutputType::Label => {
+                self.touchosc_labels.remove(addr);
+            }
+            TouchOscOutputType::Meter => {
+                self.touchosc_meters.remove(addr);
+            }
+        }
+        return Some(output_type);
+    }
+
+    // replace inputs
+    // registers addr whether or not it is in use, learned routes to addr are kept,
+    // a pending learn of addr is cancelled
+
+    pub fn replace_button(&mut self, addr: &str, default: bool) {
+        self.remove_input(addr);
+        self.add_button(addr, default);
+    }
+    pub fn replace_fader(&mut self, addr: &str, min: f32, max: f32, default: f32) {
+        self.remove_input(addr);
+        self.add_fader(addr, min, max, default);
+    }
+    pub fn replace_grid(&mut self, addr: &str, size: usize, min: f32, max: f32, default: f32) {
+        self.remove_input(addr);
+        self.add_grid(addr, size, min, max, default);
+    }
+    pub fn replace_encoder(&mut self, addr: &str, min: f32, max: f32, default: f32) {
+        self.remove_input(addr);
+        self.add_encoder(addr, min, max, default);
+    }
+    pub fn replace_radar(
+        &mut self,
+        addr: &str,
+        (rad_min, rad_max, rad_def): (f32, f32, f32),
+        (rot_min, rot_max, rot_def): (f32, f32, f32),
+    ) {
+        self.remove_input(addr);
+        self.add_radar(
+            addr,
+            (rad_min, rad_max, rad_def),
+            (rot_min, rot_max, rot_def),
+        );
+    }
+    pub fn replace_radial(&mut self, addr: &str, min: f32, max: f32, default: f32) {
+        self.remove_input(addr);
+        self.add_radial(addr, min, max, default);
+    }
+    pub fn replace_radio(&mut self, addr: &str, size: usize, default: i32) {
+        self.remove_input(addr);
+        self.add_radio(addr, size, default);
+    }
+    pub fn replace_xy(&mut self, addr: &str, min: f32, max: f32, default: f32) {
+        self.remove_input(addr);
+        self.add_xy(addr, min, max, default);
+    }
+    pub fn replace_xy_axes(&mut self, addr: &str, x: (f32, f32, f32), y: (f32, f32, f32)) {
+        self.remove_input(addr);
+        self.add_xy_axes(addr, x, y);
+    }
+    pub fn replace_text(&mut self, addr: &str, default: &str) {
+        self.remove_input(addr);
+        self.add_text(addr, default);
+    }
+
+    // edit inputs
+    // range and curve edits keep the device position and re-map the value
+
+    pub fn set_range(&mut self, addr: &str, min: f32, max: f32) {
+        self.verify_has_addr(addr);
+        match self.lookup_table[addr] {
+            TouchOscInputType::Fader => {
+                let fader = self.touchosc_faders.get_mut(addr).unwrap();
+                fader.set_min(min);
+                fader.set_max(max);
+            }
+            TouchOscInputType::Grid => {
+                let grid = self.touchosc_grids.get_mut(addr).unwrap();
+                grid.set_min(min);
+                grid.set_max(max);
+            }
+            TouchOscInputType::Encoder => {
+                let encoder = self.touchosc_encoders.get_mut(addr).unwrap();
+                encoder.set_min(min);
+                encoder.set_max(max);
+            }
+            TouchOscInputType::Radial => {
+                let radial = self.touchosc_radials.get_mut(addr).unwrap();
+                radial.set_min(min);
+                radial.set_max(max);
+            }
+            TouchOscInputType::XY => {
+                let xy = self.touchosc_xys.get_mut(addr).unwrap();
+                xy.set_min(min);
+                xy.set_max(max);
+            }
+            input_type => {
+                warn!(target: "nannou_touchosc::dispatch", addr, input_type = ?input_type, "no range to set");
+                return;
+            }
+        }
+        self.edited(addr);
+    }
+    pub fn set_radius_range(&mut self, addr: &str, min: f32, max: f32) {
+        match self.touchosc_radars.get_mut(addr) {
+            Some(radar) => {
+                radar.set_rad_min(min);
+                radar.set_rad_max(max);
+            }
+            None => panic!("\"{}\" is not a radar!", addr),
+        }
+        self.edited(addr);
+    }
+    pub fn set_rotation_range(&mut self, addr: &str, min: f32, max: f32) {
+        match self.touchosc_radars.get_mut(addr) {
+            Some(radar) => {
+                radar.set_rot_min(min);
+                radar.set_rot_max(max);
+            }
+            None => panic!("\"{}\" is not a radar!", addr),
+        }
+        self.edited(addr);
+    }
+    pub fn set_angle_unit(&mut self, addr: &str, unit: TouchOscAngle) {
+        self.radar_mut(addr).set_angle_unit(unit);
+    }
+    pub fn wrap_rotation(&mut self, addr: &str, wrap: bool) {
+        self.radar_mut(addr).set_wrap(wrap);
+    }
+    fn radar_mut(&mut self, addr: &str) -> &mut TouchOscRadar {
+        match self.touchosc_radars.get_mut(addr) {
+            Some(radar) => radar,
+            None => panic!("\"{}\" is not a radar!", addr),
+        }
+    }
+    // per axis xy edits, set_range and set_curve change both axes
+    pub fn set_xy_range(&mut self, addr: &str, x: (f32, f32), y: (f32, f32)) {
+        let xy = self.xy_mut(addr);
+        xy.set_x_range(x.0, x.1);
+        xy.set_y_range(y.0, y.1);
+        self.edited(addr);
+    }
+    // maps the pad onto rect, e.g. set_xy_rect("/xy", app.window_rect())
+    #[cfg(feature = "nannou")]
+    pub fn set_xy_rect(&mut self, addr: &str, rect: nannou::geom::Rect) {
+        self.xy_mut(addr).set_rect(rect);
+        self.edited(addr);
+    }
+    pub fn set_xy_curves(&mut self, addr: &str, x: TouchOscCurve, y: TouchOscCurve) {
+        self.xy_mut(addr).set_curves(x, y);
+        self.edited(addr);
+    }
+    pub fn invert_xy(&mut self, addr: &str, x: bool, y: bool) {
+        self.xy_mut(addr).set_inverted(x, y);
+        self.edited(addr);
+    }
+    fn xy_mut(&mut self, addr: &str) -> &mut TouchOscXY {
+        match self.touchosc_xys.get_mut(addr) {
+            Some(xy) => xy,
+            None => panic!("\"{}\" is not an xy!", addr),
+        }
+    }
+    // radar curves apply to the radius
+    pub fn set_curve(&mut self, addr: &str, curve: TouchOscCurve) {
+        self.verify_has_addr(addr);
+        match self.lookup_table[addr] {
+            TouchOscInputType::Fader => {
+                self.touchosc_faders.get_mut(addr).unwrap().set_curve(curve)
+            }
+            TouchOscInputType::Grid => self.touchosc_grids.get_mut(addr).unwrap().set_curve(curve),
+            TouchOscInputType::Encoder => self
+                .touchosc_encoders
+                .get_mut(addr)
+                .unwrap()
+                .set_curve(curve),
+            TouchOscInputType::Radar => {
+                self.touchosc_radars.get_mut(addr).unwrap().set_curve(curve)
+            }
+            TouchOscInputType::Radial => self
+                .touchosc_radials
+                .get_mut(addr)
+                .unwrap()
+                .set_curve(curve),
+            TouchOscInputType::XY => self.touchosc_xys.get_mut(addr).unwrap().set_curve(curve),
+            input_type => {
+                warn!(target: "nannou_touchosc::dispatch", addr, input_type = ?input_type, "no curve to set");
+                return;
+            }
+        }
+        self.edited(addr);
+    }
+    // default in mapped units, e.g. set_default("/xy", pt2(0.0, 1.0))
+    // a grid default applies to every element unless addr is an element address
+    pub fn set_default<V: Into<TouchOscValue>>(&mut self, addr: &str, default: V) {
+        let default = default.into();
+        let input_type = match self.lookup_table.get(addr) {
+            Some(input_type) => *input_type,
+            None => {
+                //grid element
+                let element = addr
+                    .rsplit_once('/')
+                    .and_then(|(base, _)| self.touchosc_grids.get_mut(base))
+                    .and_then(|grid| grid.element_mut(addr));
+                match (element, &default) {
+                    (Some(fader), TouchOscValue::Float(value)) => fader.set_default(*value),
+                    (Some(_), _) => self.wrong_default(addr, TouchOscInputType::Grid, &default),
+                    (None, _) => panic!("\"{}\" is not an address!", addr),
+                }
+                self.edited(addr);
+                return;
+            }
+        };
+        match (input_type, &default) {
+            (TouchOscInputType::Button, TouchOscValue::Bool(value)) => self
+                .touchosc_buttons
+                .get_mut(addr)
+                .unwrap()
+                .set_default(*value),
+            (TouchOscInputType::Fader, TouchOscValue::Float(value)) => self
+                .touchosc_faders
+                .get_mut(addr)
+                .unwrap()
+                .set_default(*value),
+            (TouchOscInputType::Grid, TouchOscValue::Float(value)) => self
+                .touchosc_grids
+                .get_mut(addr)
+                .unwrap()
+                .set_default(*value),
+            (TouchOscInputType::Encoder, TouchOscValue::Float(value)) => self
+                .touchosc_encoders
+                .get_mut(addr)
+                .unwrap()
+                .set_default(*value),
+            (TouchOscInputType::Radar, TouchOscValue::Vec2(value)) => self
+                .touchosc_radars
+                .get_mut(addr)
+                .unwrap()
+                .set_default(*value),
+            (TouchOscInputType::Radial, TouchOscValue::Float(value)) => self
+                .touchosc_radials
+                .get_mut(addr)
+                .unwrap()
+                .set_default(*value),
+            (TouchOscInputType::Radio, TouchOscValue::Int(value)) => self
+                .touchosc_radios
+                .get_mut(addr)
+                .unwrap()
+                .set_default(*value),
+            (TouchOscInputType::XY, TouchOscValue::Vec2(value)) => {
+                self.touchosc_xys.get_mut(addr).unwrap().set_default(*value)
+            }
+            (TouchOscInputType::Text, TouchOscValue::Text(value)) => self
+                .touchosc_texts
+                .get_mut(addr)
+                .unwrap()
+                .set_default(value),
+            (input_type, _) => {
+                self.wrong_default(addr, input_type, &default);
+                return;
+            }
+        }
+        self.edited(addr);
+    }
+    fn wrong_default(&self, addr: &str, input_type: TouchOscInputType, default: &TouchOscValue) {
+        warn!(
+            target: "nannou_touchosc::dispatch",
+            addr,
+            input_type = ?input_type,
+            default = ?default,
+            "default of the wrong type"
+        );
+    }
+    fn edited(&mut self, addr: &str) {
+        if self.feedback_on_edit {
+            self.send_feedback(addr);
+        }
+    }
+    // queues the device position of the input at addr (or grid element),
+    // e.g. after changing it from the sketch
+    pub fn send_feedback(&mut self, addr: &str) {
+        let messages: Vec<(String, Vec<osc::Type>)> = match self.lookup_table.get(addr) {
+            Some(TouchOscInputType::Grid) => {
+                let grid = &self.touchosc_grids[addr];
+                let mut elements: Vec<(String, Vec<osc::Type>)> = grid
+                    .faders
+                    .iter()
+                    .map(|(element, fader)| {
+                        (element.to_string(), vec![osc::Type::Float(fader.raw())])
+                    })
+                    .collect();
+                elements.sort_by(|a, b| a.0.cmp(&b.0));
+                elements
+            }
+            Some(TouchOscInputType::Radio) => {
+                vec![(
+                    addr.to_string(),
+                    vec![osc::Type::Int(self.touchosc_radios[addr].value())],
+                )]
+            }
+            Some(TouchOscInputType::Text) => vec![(
+                addr.to_string(),
+                vec![osc::Type::String(
+                    self.touchosc_texts[addr].value().to_string(),
+                )],
+            )],
+            _ => match self.raw(addr) {
+                Some(TouchOscValue::Float(x)) => {
+                    vec![(addr.to_string(), vec![osc::Type::Float(x)])]
+                }
+                Some(TouchOscValue::Vec2(v)) => vec![(
+                    addr.to_string(),
+                    vec![osc::Type::Float(v.x), osc::Type::Float(v.y)],
+                )],
+                _ => Vec::new(),
+            },
+        };
+        for (addr, args) in messages {
+            self.send(&addr, args);
+        }
+    }
+
+    // reset inputs
+    // restores the defaults passed to add_* (or set_default) and queues feedback
+
+    // addr can be an input or a grid element
+    pub fn reset(&mut self, addr: &str) {
+        if !self.reset_input(addr) {
+            panic!("\"{}\" is not an address!", addr);
+        }
+        self.send_feedback(addr);
+    }
+    pub fn reset_all(&mut self) {
+        let addrs: Vec<String> = self.lookup_table.keys().cloned().collect();
+        for addr in &addrs {
+            self.reset(addr);
+        }
+    }
+    // resets the input at prefix and every input below it, returns the number of inputs reset
+    pub fn reset_prefix(&mut self, prefix: &str) -> usize {
+        let prefix = prefix.trim_end_matches('/');
+        let addrs: Vec<String> = self
+            .lookup_table
+            .keys()
+            .filter(|addr| in_namespace(addr, prefix))
+            .cloned()
+            .collect();
+        for addr in &addrs {
+            self.reset(addr);
+        }
+        return addrs.len();
+    }
+    // false if addr is not an input or grid element
+    fn reset_input(&mut self, addr: &str) -> bool {
+        let generation = self.generation;
+        match self.lookup_table.get(addr) {
+            Some(TouchOscInputType::Button) => {
+                let button = self.touchosc_buttons.get_mut(addr).unwrap();
+                if button.reset() {
+                    button.mark(generation);
+                }
+            }
+            Some(TouchOscInputType::Fader) => {
+                let fader = self.touchosc_faders.get_mut(addr).unwrap();
+                if fader.reset() {
+                    fader.mark(generation);
+                }
+            }
+            Some(TouchOscInputType::Grid) => {
+                let grid = self.touchosc_grids.get_mut(addr).unwrap();
+                let mut changed = false;
+                for fader in grid.faders.values_mut() {
+                    if fader.reset() {
+                        fader.mark(generation);
+                        changed = true;
+                    }
+                }
+                if changed {
+                    grid.mark(generation);
+                }
+            }
+            Some(TouchOscInputType::Encoder) => {
+                let encoder = self.touchosc_encoders.get_mut(addr).unwrap();
+                if encoder.reset() {
+                    encoder.mark(generation);
+                }
+            }
+            Some(TouchOscInputType::Radar) => {
+                let radar = self.touchosc_radars.get_mut(addr).unwrap();
+                if radar.reset() {
+                    radar.mark(generation);
+                }
+            }
+            Some(TouchOscInputType::Radial) => {
+                let radial = self.touchosc_radials.get_mut(addr).unwrap();
+                if radial.reset() {
+                    radial.mark(generation);
+                }
+            }
+            Some(TouchOscInputType::Radio) => {
+                let radio = self.touchosc_radios.get_mut(addr).unwrap();
+                if radio.reset() {
+                    radio.mark(generation);
+                }
+            }
+            Some(TouchOscInputType::XY) => {
+                let xy = self.touchosc_xys.get_mut(addr).unwrap();
+                if xy.reset() {
+                    xy.mark(generation);
+                }
+            }
+            Some(TouchOscInputType::Text) => {
+                let text = self.touchosc_texts.get_mut(addr).unwrap();
+                if text.reset() {
+                    text.mark(generation);
+                }
+            }
+            //grid element
+            None => {
+                let grid = match addr.rsplit_once('/') {
+                    Some((base, _)) => self.touchosc_grids.get_mut(base),
+                    None => None,
+                };
+                let grid = match grid {
+                    Some(grid) if grid.faders.contains_key(addr) => grid,
+                    _ => return false,
+                };
+                let fader = grid.faders.get_mut(addr).unwrap();
+                if fader.reset() {
+                    fader.mark(generation);
+                    grid.mark(generation);
+                }
+            }
+        }
+        return true;
+    }
 
     // get input values
 
@@ -351,6 +1330,14 @@ impl TouchOscClient {
         }
         return pt2(0.0, 0.0);
     }
+    // radar as (radius, angle in radians), see set_angle_unit
+    pub fn polar(&self, addr: &str) -> Polar {
+        self.verify_has_addr(addr);
+        return match self.touchosc_radars.get(addr) {
+            Some(radar) => radar.polar(),
+            None => Polar::default(),
+        };
+    }
     pub fn radial(&self, addr: &str) -> f32 {
         self.verify_has_addr(addr);
         for (key, input_type) in &self.lookup_table {
@@ -377,6 +1364,13 @@ impl TouchOscClient {
         }
         return 0;
     }
+    pub fn radio_enum<T: TouchOscRadioEnum>(&self, addr: &str) -> T {
+        let index = self.radio(addr) as usize;
+        match T::VARIANTS.get(index) {
+            Some(variant) => *variant,
+            None => T::VARIANTS[0],
+        }
+    }
     pub fn xy(&self, addr: &str) -> Vec2 {
         self.verify_has_addr(addr);
         for (key, input_type) in &self.lookup_table {
@@ -390,25 +1384,644 @@ impl TouchOscClient {
         }
         return pt2(0.0, 0.0);
     }
-
-    // helpers
-
-    pub fn verify_has_addr(&self, addr: &str) {
-        //TODO: try contains?
-        if !self.lookup_table.keys().any(|val| *val == *addr) {
-            panic!("\"{}\" is not an address!", addr);
-        }
+    pub fn text(&self, addr: &str) -> &str {
+        self.verify_has_addr(addr);
+        return match self.lookup_table[addr] {
+            //verify correct type at addr
+            TouchOscInputType::Text => self.touchosc_texts[addr].value(),
+            _ => "",
+        };
     }
-    pub fn verify_free_addr(&self, addr: &str) {
-        if self.lookup_table.keys().any(|val| *val == *addr) {
-            panic!("\"{}\" address in use!", addr);
-        }
+    // value of any input (or grid element) without knowing its type
+    pub fn value(&self, addr: &str) -> Option<TouchOscValue> {
+        return match self.lookup_table.get(addr) {
+            Some(TouchOscInputType::Button) => {
+                Some(TouchOscValue::Bool(self.touchosc_buttons[addr].state()))
+            }
+            Some(TouchOscInputType::Fader) => {
+                Some(TouchOscValue::Float(self.touchosc_faders[addr].value()))
+            }
+            Some(TouchOscInputType::Encoder) => {
+                Some(TouchOscValue::Float(self.touchosc_encoders[addr].value()))
+            }
+            Some(TouchOscInputType::Radar) => {
+                Some(TouchOscValue::Vec2(self.touchosc_radars[addr].values()))
+            }
+            Some(TouchOscInputType::Radial) => {
+                Some(TouchOscValue::Float(self.touchosc_radials[addr].value()))
+            }
+            Some(TouchOscInputType::Radio) => {
+                Some(TouchOscValue::Int(self.touchosc_radios[addr].value()))
+            }
+            Some(TouchOscInputType::XY) => {
+                Some(TouchOscValue::Vec2(self.touchosc_xys[addr].values()))
+            }
+            Some(TouchOscInputType::Text) => Some(TouchOscValue::Text(
+                self.touchosc_texts[addr].value().to_string(),
+            )),
+            //a grid only has values per element
+            Some(TouchOscInputType::Grid) => None,
+            None => {
+                let (base, _) = addr.rsplit_once('/')?;
+                let grid = self.touchosc_grids.get(base)?;
+                let fader = grid.faders.get(addr)?;
+                Some(TouchOscValue::Float(fader.value()))
+            }
+        };
+    }
+
+    // value at addr as the device sent it (0..1, or 0/1 for buttons), before range
+    // mapping; radio indices as a 0..1 position, None for text inputs
+    pub fn raw(&self, addr: &str) -> Option<TouchOscValue> {
+        return match self.lookup_table.get(addr) {
+            Some(TouchOscInputType::Button) => {
+                Some(TouchOscValue::Float(self.touchosc_buttons[addr].raw()))
+            }
+            Some(TouchOscInputType::Fader) => {
+                Some(TouchOscValue::Float(self.touchosc_faders[addr].raw()))
+            }
+            Some(TouchOscInputType::Encoder) => {
+                Some(TouchOscValue::Float(self.touchosc_encoders[addr].raw()))
+            }
+            Some(TouchOscInputType::Radar) => {
+                Some(TouchOscValue::Vec2(self.touchosc_radars[addr].raw()))
+            }
+            Some(TouchOscInputType::Radial) => {
+                Some(TouchOscValue::Float(self.touchosc_radials[addr].raw()))
+            }
+            Some(TouchOscInputType::Radio) => {
+                Some(TouchOscValue::Float(self.touchosc_radios[addr].raw()))
+            }
+            Some(TouchOscInputType::XY) => Some(TouchOscValue::Vec2(self.touchosc_xys[addr].raw())),
+            Some(TouchOscInputType::Grid) | Some(TouchOscInputType::Text) => None,
+            None => {
+                let (base, _) = addr.rsplit_once('/')?;
+                let fader = self.touchosc_grids.get(base)?.faders.get(addr)?;
+                Some(TouchOscValue::Float(fader.raw()))
+            }
+        };
+    }
+
+    // per update() message handling
+
+    // every value applied to the input at addr during the last update(), oldest
+    // first (grid elements by element address), empty unless keep_history() is set
+    pub fn history(&self, addr: &str) -> &[TouchOscValue] {
+        return match self.history.get(addr) {
+            Some(values) => values,
+            None => &[],
+        };
+    }
+    // messages to addr (as received) dropped by coalesce() in the last update()
+    pub fn collapsed(&self, addr: &str) -> usize {
+        return self.collapsed.get(addr).copied().unwrap_or(0);
+    }
+    // messages dropped by coalesce() since the client was created
+    pub fn collapsed_total(&self) -> u64 {
+        return self.collapsed_total;
+    }
+
+    // change detection
+
+    // bumped on every update() (every packet with a receiver thread)
+    pub fn generation(&self) -> u64 {
+        return self.generation;
+    }
+    // true if the value at addr changed during the last update()
+    pub fn changed(&self, addr: &str) -> bool {
+        return self.changed_since(addr, self.generation.saturating_sub(1));
+    }
+    // true if the value at addr changed after the given client generation
+    pub fn changed_since(&self, addr: &str, generation: u64) -> bool {
+        return match self.input_generation(addr) {
+            Some((input_generation, _)) => input_generation > generation,
+            None => false,
+        };
+    }
+    // time of the last change at addr, None if it never changed
+    pub fn updated(&self, addr: &str) -> Option<Instant> {
+        return self.input_generation(addr).and_then(|(_, updated)| updated);
+    }
+    fn input_generation(&self, addr: &str) -> Option<(u64, Option<Instant>)> {
+        return match self.lookup_table.get(addr) {
+            Some(TouchOscInputType::Button) => {
+                let button = &self.touchosc_buttons[addr];
+                Some((button.generation(), button.updated()))
+            }
+            Some(TouchOscInputType::Fader) => {
+                let fader = &self.touchosc_faders[addr];
+                Some((fader.generation(), fader.updated()))
+            }
+            Some(TouchOscInputType::Grid) => {
+                let grid = &self.touchosc_grids[addr];
+                Some((grid.generation(), grid.updated()))
+            }
+            Some(TouchOscInputType::Encoder) => {
+                let encoder = &self.touchosc_encoders[addr];
+                Some((encoder.generation(), encoder.updated()))
+            }
+            Some(TouchOscInputType::Radar) => {
+                let radar = &self.touchosc_radars[addr];
+                Some((radar.generation(), radar.updated()))
+            }
+            Some(TouchOscInputType::Radial) => {
+                let radial = &self.touchosc_radials[addr];
+                Some((radial.generation(), radial.updated()))
+            }
+            Some(TouchOscInputType::Radio) => {
+                let radio = &self.touchosc_radios[addr];
+                Some((radio.generation(), radio.updated()))
+            }
+            Some(TouchOscInputType::XY) => {
+                let xy = &self.touchosc_xys[addr];
+                Some((xy.generation(), xy.updated()))
+            }
+            Some(TouchOscInputType::Text) => {
+                let text = &self.touchosc_texts[addr];
+                Some((text.generation(), text.updated()))
+            }
+            //grid element
+            None => {
+                let (base, _) = addr.rsplit_once('/')?;
+                let fader = self.touchosc_grids.get(base)?.faders.get(addr)?;
+                Some((fader.generation(), fader.updated()))
+            }
+        };
+    }
+
+    // every input value at this point in time, see TouchOscSnapshot
+    pub fn snapshot(&self) -> TouchOscSnapshot {
+        let mut values = HashMap::new();
+        for (addr, input_type) in &self.lookup_table {
+            match input_type {
+                TouchOscInputType::Grid => {
+                    for (element_addr, fader) in &self.touchosc_grids[addr].faders {
+                        values.insert(
+                            element_addr.to_string(),
+                            (*input_type, TouchOscValue::Float(fader.value())),
+                        );
+                    }
+                }
+                _ => {
+                    if let Some(value) = self.value(addr) {
+                        values.insert(addr.to_string(), (*input_type, value));
+                    }
+                }
+            }
+        }
+        let angle_units = self
+            .touchosc_radars
+            .iter()
+            .map(|(addr, radar)| (addr.to_string(), radar.angle_unit()))
+            .collect();
+        return TouchOscSnapshot::new(values, angle_units);
+    }
+
+    // outputs
+
+    // sends outputs to the device, e.g. "192.168.1.20:9000"
+    pub fn connect<A: ToSocketAddrs>(&mut self, target: A) {
+        let sender = osc::sender().unwrap().connect(target).unwrap(); //Bind `osc::Sender` to target.
+        self.osc_sender = Some(sender);
+        //bring the device up to date
+        self.forget_sent();
+        let labels: Vec<(String, String)> = self
+            .touchosc_labels
+            .iter()
+            .map(|(addr, label)| (addr.to_string(), label.text().to_string()))
+            .collect();
+        for (addr, text) in labels {
+            self.send(&addr, vec![osc::Type::String(text)]);
+        }
+        for meter in self.touchosc_meters.values_mut() {
+            meter.sent = None;
+            meter.last_sent = None;
+        }
+        self.send_meters();
+    }
+    pub fn is_connected(&self) -> bool {
+        return self.osc_sender.is_some();
+    }
+    pub fn add_label(&mut self, addr: &str, text: &str) {
+        self.verify_free_addr(addr);
+        self.output_table
+            .insert((&addr).to_string(), TouchOscOutputType::Label);
+        self.touchosc_labels
+            .insert((&addr).to_string(), TouchOscLabel::new(text));
+        self.send(addr, vec![osc::Type::String(text.to_string())]);
+    }
+    // queues text for the label at addr, unless the label already shows it
+    pub fn set_label(&mut self, addr: &str, text: &str) {
+        let changed = match self.touchosc_labels.get_mut(addr) {
+            Some(label) => label.set_text(text),
+            None => panic!("\"{}\" is not a label!", addr),
+        };
+        if changed {
+            self.send(addr, vec![osc::Type::String(text.to_string())]);
+        }
+    }
+    pub fn label(&self, addr: &str) -> &str {
+        return match self.touchosc_labels.get(addr) {
+            Some(label) => label.text(),
+            None => panic!("\"{}\" is not a label!", addr),
+        };
+    }
+
+    // min and max are mapped to 0..1 on the device, values are sent at most
+    // DEFAULT_METER_RATE times per second (see set_meter_rate)
+    pub fn add_meter(&mut self, addr: &str, min: f32, max: f32) {
+        self.verify_free_addr(addr);
+        self.output_table
+            .insert((&addr).to_string(), TouchOscOutputType::Meter);
+        self.touchosc_meters
+            .insert((&addr).to_string(), TouchOscMeter::new(min, max));
+    }
+    // queues the value unless the last send was too recent, in which case
+    // it is queued by a later set_meter() or update()
+    pub fn set_meter(&mut self, addr: &str, value: f32) {
+        match self.touchosc_meters.get_mut(addr) {
+            Some(meter) => meter.set_value(value),
+            None => panic!("\"{}\" is not a meter!", addr),
+        }
+        self.send_meter(addr, Instant::now());
+    }
+    pub fn set_meter_rate(&mut self, addr: &str, rate: f32) {
+        match self.touchosc_meters.get_mut(addr) {
+            Some(meter) => meter.set_rate(rate),
+            None => panic!("\"{}\" is not a meter!", addr),
+        }
+    }
+    pub fn meter(&self, addr: &str) -> f32 {
+        return match self.touchosc_meters.get(addr) {
+            Some(meter) => meter.value(),
+            None => panic!("\"{}\" is not a meter!", addr),
+        };
+    }
+    // queues every meter with an unsent value whose interval has passed
+    pub fn send_meters(&mut self) {
+        if self.osc_sender.is_none() {
+            return;
+        }
+        let now = Instant::now();
+        let addrs: Vec<String> = self.touchosc_meters.keys().cloned().collect();
+        for addr in addrs {
+            self.send_meter(&addr, now);
+        }
+    }
+    fn send_meter(&mut self, addr: &str, now: Instant) {
+        if self.osc_sender.is_none() {
+            return;
+        }
+        let value = match self.touchosc_meters.get_mut(addr) {
+            Some(meter) => meter.take_due(now),
+            None => None,
+        };
+        if let Some(value) = value {
+            self.send(addr, vec![osc::Type::Float(value)]);
+        }
+    }
+
+    // unknown addresses
+
+    // messages received for addresses with no registered input, sorted by address
+    pub fn unknown_addrs(&self) -> Vec<&TouchOscUnknownAddr> {
+        let mut unknown: Vec<&TouchOscUnknownAddr> = self.unknown_addrs.values().collect();
+        unknown.sort_by(|a, b| a.addr.cmp(&b.addr));
+        return unknown;
+    }
+    pub fn clear_unknown_addrs(&mut self) {
+        self.unknown_addrs.clear();
+    }
+    fn capture_unknown(&mut self, addr: &str, args: Vec<osc::Type>) {
+        if self.verbose {
+            debug!(
+                target: "nannou_touchosc::learn",
+                addr,
+                types = %osc_type_tags(&args),
+                "not registered"
+            );
+        }
+        let unknown = self
+            .unknown_addrs
+            .entry(addr.to_string())
+            .or_insert(TouchOscUnknownAddr {
+                addr: addr.to_string(),
+                types: String::new(),
+                count: 0,
+                last_value: Vec::new(),
+            });
+        unknown.types = osc_type_tags(&args);
+        unknown.count += 1;
+        unknown.last_value = args;
+    }
+
+    // osc learn
+    // the next unknown address with a matching signature is bound to addr,
+    // after which its messages drive the input registered at addr
+
+    // infers the input type from the first unknown message (see infer_input_type)
+    pub fn learn(&mut self, addr: &str) {
+        self.verify_free_addr(addr);
+        self.pending_learns.push(TouchOscLearn {
+            addr: addr.to_string(),
+            input_type: None,
+        });
+    }
+    pub fn learn_fader(&mut self, addr: &str, min: f32, max: f32) {
+        self.add_fader(addr, min, max, min);
+        self.pending_learns.push(TouchOscLearn {
+            addr: addr.to_string(),
+            input_type: Some(TouchOscInputType::Fader),
+        });
+    }
+    pub fn learn_xy(&mut self, addr: &str, min: f32, max: f32) {
+        self.add_xy(addr, min, max, min);
+        self.pending_learns.push(TouchOscLearn {
+            addr: addr.to_string(),
+            input_type: Some(TouchOscInputType::XY),
+        });
+    }
+    pub fn is_learning(&self) -> bool {
+        return !self.pending_learns.is_empty();
+    }
+    pub fn cancel_learn(&mut self) {
+        self.pending_learns.clear();
+    }
+    // the incoming address bound to addr, if learned
+    pub fn learned(&self, addr: &str) -> Option<&str> {
+        for (incoming, registered) in &self.learned_routes {
+            if registered == addr {
+                return Some(incoming);
+            }
+        }
+        return None;
+    }
+    fn bind_learn(&mut self, incoming: &str, args: &[osc::Type]) -> bool {
+        //touch messages arrive before the value, learn the control instead
+        if is_touch_addr(incoming) {
+            return false;
+        }
+        let inferred = match infer_input_type(args) {
+            Some(input_type) => input_type,
+            None => return false,
+        };
+        let index = match self
+            .pending_learns
+            .iter()
+            .position(|learn| learn.input_type.is_none() || learn.input_type == Some(inferred))
+        {
+            Some(index) => index,
+            None => return false,
+        };
+        let learn = self.pending_learns.remove(index);
+        if learn.input_type.is_none() {
+            match inferred {
+                TouchOscInputType::XY => self.add_xy(&learn.addr, 0.0, 1.0, 0.0),
+                TouchOscInputType::Text => self.add_text(&learn.addr, ""),
+                _ => self.add_fader(&learn.addr, 0.0, 1.0, 0.0),
+            }
+        }
+        if self.verbose {
+            debug!(target: "nannou_touchosc::learn", addr = %learn.addr, incoming, "learned");
+        }
+        self.unknown_addrs.remove(incoming);
+        self.learned_routes.insert(incoming.to_string(), learn.addr);
+        return true;
+    }
+
+    // helpers
+
+    pub fn verify_has_addr(&self, addr: &str) {
+        //TODO: try contains?
+        if !self.lookup_table.keys().any(|val| *val == *addr) {
+            panic!("\"{}\" is not an address!", addr);
+        }
+    }
+    pub fn verify_free_addr(&self, addr: &str) {
+        if self.lookup_table.keys().any(|val| *val == *addr)
+            || self.output_table.contains_key(addr)
+            || self.pages.is_pager(addr)
+            || self.pending_learns.iter().any(|learn| learn.addr == addr)
+        {
+            panic!("\"{}\" address in use!", addr);
+        }
     }
 }
+
+// layouts
+#[cfg(feature = "tosc")]
+impl TouchOscClient {
+    // writes a TouchOSC layout with one control per registered input,
+    // each sending to its registered address
+    pub fn export_tosc<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.layout().save(path)
+    }
+    // lists registered inputs missing from the layout (and vice versa),
+    // as well as type and radio/grid size mismatches
+    pub fn validate_against_tosc<P: AsRef<Path>>(&self, path: P) -> io::Result<TouchOscValidation> {
+        let layout = TouchOscLayout::open(path)?;
+        Ok(TouchOscValidation::compare(&self.layout(), &layout))
+    }
+    // registered inputs, labels and meters, sorted by address
+    // defaults are the registered ones, not the current positions
+    pub fn layout(&self) -> TouchOscLayout {
+        let controls = self.lookup_table.keys().map(|addr| {
+            let input_type = self.lookup_table[addr];
+            let (size, default_x, default_y) = match input_type {
+                TouchOscInputType::Button => {
+                    let default = self.touchosc_buttons[addr].default();
+                    (1, if default { 1.0 } else { 0.0 }, 0.0)
+                }
+                TouchOscInputType::Fader => {
+                    let fader = &self.touchosc_faders[addr];
+                    (1, fader.raw_default(), 0.0)
+                }
+                TouchOscInputType::Grid => {
+                    let grid = &self.touchosc_grids[addr];
+                    let default = match grid.faders.get(&format!("{}/1", addr)) {
+                        Some(fader) => fader.raw_default(),
+                        None => 0.0,
+                    };
+                    (grid.size(), default, 0.0)
+                }
+                TouchOscInputType::Encoder => {
+                    let encoder = &self.touchosc_encoders[addr];
+                    (1, encoder.raw_default(), 0.0)
+                }
+                TouchOscInputType::Radar => {
+                    let radar = &self.touchosc_radars[addr];
+                    (1, radar.raw_default().x, radar.raw_default().y)
+                }
+                TouchOscInputType::Radial => {
+                    let radial = &self.touchosc_radials[addr];
+                    (1, radial.raw_default(), 0.0)
+                }
+                TouchOscInputType::Radio => {
+                    let radio = &self.touchosc_radios[addr];
+                    (radio.size(), radio.default() as f32, 0.0)
+                }
+                TouchOscInputType::XY => {
+                    let xy = &self.touchosc_xys[addr];
+                    (1, xy.raw_default().x, xy.raw_default().y)
+                }
+                TouchOscInputType::Text => (1, 0.0, 0.0),
+            };
+            TouchOscLayoutControl {
+                name: addr.rsplit('/').next().unwrap_or("").to_string(),
+                node_type: format!("{:?}", input_type).to_uppercase(),
+                input_type: Some(input_type),
+                output_type: None,
+                addr: addr.to_string(),
+                size,
+                default_x,
+                default_y,
+            }
+        });
+        let outputs = self.output_table.iter().map(|(addr, output_type)| {
+            //meters are faders the performer can't move, starting empty
+            let node_type = match output_type {
+                TouchOscOutputType::Label => "LABEL",
+                TouchOscOutputType::Meter => "FADER",
+            };
+            TouchOscLayoutControl {
+                name: addr.rsplit('/').next().unwrap_or("").to_string(),
+                node_type: node_type.to_string(),
+                input_type: None,
+                output_type: Some(*output_type),
+                addr: addr.to_string(),
+                size: 1,
+                default_x: 0.0,
+                default_y: 0.0,
+            }
+        });
+        let mut controls: Vec<TouchOscLayoutControl> = controls.chain(outputs).collect();
+        controls.sort_by(|a, b| a.addr.cmp(&b.addr));
+        TouchOscLayout::new(controls)
+    }
+}
+// argument coercion
+// TouchOSC sends floats (ints for radios), other OSC senders may use any numeric type
+
+pub fn coerce_f32(arg: &osc::Type) -> Option<f32> {
+    match arg {
+        osc::Type::Float(x) => Some(*x),
+        osc::Type::Int(x) => Some(*x as f32),
+        osc::Type::Double(x) => Some(*x as f32),
+        osc::Type::Long(x) => Some(*x as f32),
+        osc::Type::Bool(x) => Some(if *x { 1.0 } else { 0.0 }),
+        osc::Type::String(x) => x.trim().parse().ok(),
+        _ => None,
+    }
+}
+pub fn coerce_string(arg: &osc::Type) -> Option<String> {
+    match arg {
+        osc::Type::String(x) => Some(x.clone()),
+        osc::Type::Char(x) => Some(x.to_string()),
+        osc::Type::Float(x) => Some(x.to_string()),
+        osc::Type::Int(x) => Some(x.to_string()),
+        osc::Type::Double(x) => Some(x.to_string()),
+        osc::Type::Long(x) => Some(x.to_string()),
+        osc::Type::Bool(x) => Some(x.to_string()),
+        _ => None,
+    }
+}
+pub fn coerce_i32(arg: &osc::Type) -> Option<i32> {
+    match arg {
+        osc::Type::Int(x) => Some(*x),
+        osc::Type::Long(x) => i32::try_from(*x).ok(),
+        osc::Type::Bool(x) => Some(*x as i32),
+        osc::Type::String(x) => match x.trim().parse::<i32>() {
+            Ok(x) => Some(x),
+            Err(_) => x.trim().parse::<f32>().ok().map(|x| x.round() as i32),
+        },
+        _ => coerce_f32(arg).map(|x| x.round() as i32),
+    }
+}
+fn float_arg(args: &[osc::Type], strict: bool) -> Option<f32> {
+    match args {
+        [osc::Type::Float(x)] => Some(*x),
+        [arg] if !strict => coerce_f32(arg),
+        _ => None,
+    }
+}
+fn int_arg(args: &[osc::Type], strict: bool) -> Option<i32> {
+    match args {
+        [osc::Type::Int(x)] => Some(*x),
+        [arg] if !strict => coerce_i32(arg),
+        _ => None,
+    }
+}
+fn text_arg(args: &[osc::Type], strict: bool) -> Option<String> {
+    match args {
+        [osc::Type::String(x)] => Some(x.clone()),
+        [arg] if !strict => coerce_string(arg),
+        _ => None,
+    }
+}
+fn vec2_arg(args: &[osc::Type], strict: bool) -> Option<Vec2> {
+    match args {
+        [osc::Type::Float(x), osc::Type::Float(y)] => Some(pt2(*x, *y)),
+        [x, y] if !strict => Some(pt2(coerce_f32(x)?, coerce_f32(y)?)),
+        _ => None,
+    }
+}
+// true if addr is prefix itself or lies below it
+pub(crate) fn in_namespace(addr: &str, prefix: &str) -> bool {
+    return match addr.strip_prefix(prefix) {
+        Some(rest) => rest.is_empty() || rest.starts_with('/'),
+        None => false,
+    };
+}
+// touch state sent next to a control's value, "<addr>/touch" ("/z" on TouchOSC Mk1)
+pub fn is_touch_addr(addr: &str) -> bool {
+    return addr.ends_with("/z") || addr.ends_with("/touch");
+}
+// input type implied by an argument signature: one float is a fader, two an xy pad,
+// a string is a text field
+pub fn infer_input_type(args: &[osc::Type]) -> Option<TouchOscInputType> {
+    match args {
+        [osc::Type::Float(_)] => Some(TouchOscInputType::Fader),
+        [osc::Type::Float(_), osc::Type::Float(_)] => Some(TouchOscInputType::XY),
+        [osc::Type::String(_)] => Some(TouchOscInputType::Text),
+        _ => None,
+    }
+}
+// OSC type tag of an argument, as written in the message type tag string
+pub fn osc_type_tag(arg: &osc::Type) -> char {
+    match arg {
+        osc::Type::Int(_) => 'i',
+        osc::Type::Float(_) => 'f',
+        osc::Type::String(_) => 's',
+        osc::Type::Blob(_) => 'b',
+        osc::Type::Time(..) => 't',
+        osc::Type::Long(_) => 'h',
+        osc::Type::Double(_) => 'd',
+        osc::Type::Char(_) => 'c',
+        osc::Type::Color(_) => 'r',
+        osc::Type::Midi(_) => 'm',
+        osc::Type::Bool(true) => 'T',
+        osc::Type::Bool(false) => 'F',
+        osc::Type::Nil => 'N',
+        osc::Type::Inf => 'I',
+    }
+}
+pub fn osc_type_tags(args: &[osc::Type]) -> String {
+    return args.iter().map(osc_type_tag).collect();
+}
+// inverse of the 0..1 input mapping
+fn normalize(value: f32, min: f32, max: f32) -> f32 {
+    if min == max {
+        return 0.0;
+    }
+    return map_range(value, min, max, 0.0, 1.0);
+}
 //--------------------------------------------------------
 pub struct TouchOscButton {
     state: bool,
     value: f32,
+    default: bool,
+    generation: u64,
+    updated: Option<Instant>,
 }
 impl TouchOscButton {
     pub fn new(state: bool) -> Self {
@@ -419,11 +2032,14 @@ impl TouchOscButton {
         TouchOscButton {
             state: state,
             value,
+            default: state,
+            generation: 0,
+            updated: None,
         }
     }
     pub fn print(&self, addr: &str, is_verbose: bool) {
         if is_verbose {
-            println!("{} {}", addr, self.state);
+            debug!(target: "nannou_touchosc::dispatch", addr, value = self.state);
         }
     }
     pub fn set_state(&mut self, value: f32) {
@@ -432,6 +2048,7 @@ impl TouchOscButton {
         } else {
             self.state = false;
         }
+        self.value = value;
     }
     pub fn state(&self) -> bool {
         // get
@@ -441,12 +2058,43 @@ impl TouchOscButton {
         // get
         return self.value;
     }
+    pub fn raw(&self) -> f32 {
+        return self.value; //buttons are not mapped
+    }
+    pub fn set_default(&mut self, default: bool) {
+        self.default = default;
+    }
+    pub fn default(&self) -> bool {
+        return self.default;
+    }
+    // returns true if the state changed
+    pub fn reset(&mut self) -> bool {
+        let before = self.state;
+        self.set_state(if self.default { 1.0 } else { 0.0 });
+        return self.state != before;
+    }
+    // generation of the client update in which the value last changed
+    pub fn generation(&self) -> u64 {
+        return self.generation;
+    }
+    pub fn updated(&self) -> Option<Instant> {
+        return self.updated;
+    }
+    pub(crate) fn mark(&mut self, generation: u64) {
+        self.generation = generation;
+        self.updated = Some(Instant::now());
+    }
 }
 //--------------------------------------------------------
 pub struct TouchOscFader {
     min: f32,
     max: f32,
     value: f32,
+    raw: f32,     //0..1 as received
+    default: f32, //mapped
+    curve: TouchOscCurve,
+    generation: u64,
+    updated: Option<Instant>,
 }
 impl TouchOscFader {
     pub fn new(min: f32, max: f32, default: f32) -> Self {
@@ -454,34 +2102,86 @@ impl TouchOscFader {
             min: min,
             max: max,
             value: default,
+            raw: normalize(default, min, max),
+            default,
+            curve: TouchOscCurve::Linear,
+            generation: 0,
+            updated: None,
         }
     }
     pub fn print(&self, addr: &str, is_verbose: bool) {
         if is_verbose {
-            println!("{} {}", addr, self.value);
+            debug!(target: "nannou_touchosc::dispatch", addr, value = self.value);
         }
     }
+    // range changes re-map the current value
     pub fn set_min(&mut self, min: f32) {
         self.min = min;
+        self.value = self.range(self.raw);
     }
     pub fn set_max(&mut self, max: f32) {
         self.max = max;
+        self.value = self.range(self.raw);
     }
     pub fn set_value(&mut self, value: f32) {
+        self.raw = value;
         self.value = self.range(value);
     }
+    // curve changes re-map the current value
+    pub fn set_curve(&mut self, curve: TouchOscCurve) {
+        self.curve = curve;
+        self.value = self.range(self.raw);
+    }
+    pub fn set_default(&mut self, default: f32) {
+        self.default = default;
+    }
     pub fn range(&self, arg: f32) -> f32 {
-        return map_range(arg, 0.0, 1.0, self.min, self.max);
+        return map_range(self.curve.apply(arg), 0.0, 1.0, self.min, self.max);
+    }
+    // inverse of range()
+    pub fn unmap(&self, value: f32) -> f32 {
+        return self.curve.invert(normalize(value, self.min, self.max));
     }
     pub fn value(&self) -> f32 {
         // get
         return self.value;
     }
+    pub fn raw(&self) -> f32 {
+        return self.raw;
+    }
+    pub fn default(&self) -> f32 {
+        return self.default;
+    }
+    pub fn raw_default(&self) -> f32 {
+        return self.unmap(self.default);
+    }
+    // returns true if the value changed
+    pub fn reset(&mut self) -> bool {
+        let before = self.value;
+        self.set_value(self.raw_default());
+        return self.value != before;
+    }
+    pub fn curve(&self) -> TouchOscCurve {
+        return self.curve;
+    }
+    // generation of the client update in which the value last changed
+    pub fn generation(&self) -> u64 {
+        return self.generation;
+    }
+    pub fn updated(&self) -> Option<Instant> {
+        return self.updated;
+    }
+    pub(crate) fn mark(&mut self, generation: u64) {
+        self.generation = generation;
+        self.updated = Some(Instant::now());
+    }
 }
 //--------------------------------------------------------
 pub struct TouchOscGrid {
     base_addr: String,
     faders: HashMap<String, TouchOscFader>,
+    generation: u64,
+    updated: Option<Instant>,
 }
 impl TouchOscGrid {
     pub fn new(base_addr: &str, size: usize, min: f32, max: f32, default: f32) -> Self {
@@ -493,18 +2193,23 @@ impl TouchOscGrid {
         TouchOscGrid {
             base_addr: base_addr.to_string(),
             faders,
+            generation: 0,
+            updated: None,
         }
     }
     pub fn print(&self, addr: &str, is_verbose: bool) {
         if is_verbose {
             if self.faders.contains_key(addr) {
-                println!("{} {}", addr, self.faders[addr].value());
+                debug!(target: "nannou_touchosc::grid", addr, value = self.faders[addr].value());
             }
         }
     }
     pub fn base_addr(&self) -> &str {
         return &self.base_addr;
     }
+    pub fn size(&self) -> usize {
+        return self.faders.len();
+    }
     pub fn set_value(&mut self, addr: &str, value: f32) {
         if self.faders.contains_key(addr) {
             match self.faders.get_mut(addr) {
@@ -512,15 +2217,54 @@ impl TouchOscGrid {
                 None => (),
             }
         } else {
-            println!(
-                "cannot obtain value on 'out of bounds' grid element: {}",
-                addr
-            );
+            warn!(target: "nannou_touchosc::grid", addr, "out of bounds grid element");
         }
     }
     pub fn value(&self, addr: &str) -> f32 {
         return self.faders[addr].value();
     }
+    pub fn raw(&self, addr: &str) -> f32 {
+        return self.faders[addr].raw();
+    }
+    // range changes re-map the current values
+    pub fn set_min(&mut self, min: f32) {
+        for fader in self.faders.values_mut() {
+            fader.set_min(min);
+        }
+    }
+    pub fn set_max(&mut self, max: f32) {
+        for fader in self.faders.values_mut() {
+            fader.set_max(max);
+        }
+    }
+    pub fn set_curve(&mut self, curve: TouchOscCurve) {
+        for fader in self.faders.values_mut() {
+            fader.set_curve(curve);
+        }
+    }
+    pub fn set_default(&mut self, default: f32) {
+        for fader in self.faders.values_mut() {
+            fader.set_default(default);
+        }
+    }
+    // elements by address, e.g. "/grid/1"
+    pub fn element(&self, addr: &str) -> Option<&TouchOscFader> {
+        return self.faders.get(addr);
+    }
+    pub fn element_mut(&mut self, addr: &str) -> Option<&mut TouchOscFader> {
+        return self.faders.get_mut(addr);
+    }
+    // generation of the client update in which the value last changed
+    pub fn generation(&self) -> u64 {
+        return self.generation;
+    }
+    pub fn updated(&self) -> Option<Instant> {
+        return self.updated;
+    }
+    pub(crate) fn mark(&mut self, generation: u64) {
+        self.generation = generation;
+        self.updated = Some(Instant::now());
+    }
 }
 
 //--------------------------------------------------------
@@ -528,6 +2272,11 @@ pub struct TouchOscEncoder {
     min: f32,
     max: f32,
     value: f32,
+    raw: f32,     //0..1 as received
+    default: f32, //mapped
+    curve: TouchOscCurve,
+    generation: u64,
+    updated: Option<Instant>,
 }
 impl TouchOscEncoder {
     pub fn new(min: f32, max: f32, default: f32) -> Self {
@@ -535,37 +2284,96 @@ impl TouchOscEncoder {
             min: min,
             max: max,
             value: default, //default
+            raw: normalize(default, min, max),
+            default,
+            curve: TouchOscCurve::Linear,
+            generation: 0,
+            updated: None,
         }
     }
     pub fn print(&self, addr: &str, is_verbose: bool) {
         if is_verbose {
-            println!("{} {}", addr, self.value);
+            debug!(target: "nannou_touchosc::dispatch", addr, value = self.value);
         }
     }
+    // range changes re-map the current value
     pub fn set_min(&mut self, min: f32) {
         self.min = min;
+        self.value = self.range(self.raw);
     }
     pub fn set_max(&mut self, max: f32) {
         self.max = max;
+        self.value = self.range(self.raw);
     }
     pub fn set_value(&mut self, arg: f32) {
+        self.raw = arg;
         self.value = self.range(arg);
     }
+    // curve changes re-map the current value
+    pub fn set_curve(&mut self, curve: TouchOscCurve) {
+        self.curve = curve;
+        self.value = self.range(self.raw);
+    }
+    pub fn set_default(&mut self, default: f32) {
+        self.default = default;
+    }
     pub fn range(&self, arg: f32) -> f32 {
-        return map_range(arg, 0.0, 1.0, self.min, self.max);
+        return map_range(self.curve.apply(arg), 0.0, 1.0, self.min, self.max);
+    }
+    // inverse of range()
+    pub fn unmap(&self, value: f32) -> f32 {
+        return self.curve.invert(normalize(value, self.min, self.max));
     }
     pub fn value(&self) -> f32 {
         // get
         return self.value;
     }
+    pub fn raw(&self) -> f32 {
+        return self.raw;
+    }
+    pub fn default(&self) -> f32 {
+        return self.default;
+    }
+    pub fn raw_default(&self) -> f32 {
+        return self.unmap(self.default);
+    }
+    // returns true if the value changed
+    pub fn reset(&mut self) -> bool {
+        let before = self.value;
+        self.set_value(self.raw_default());
+        return self.value != before;
+    }
+    pub fn curve(&self) -> TouchOscCurve {
+        return self.curve;
+    }
+    // generation of the client update in which the value last changed
+    pub fn generation(&self) -> u64 {
+        return self.generation;
+    }
+    pub fn updated(&self) -> Option<Instant> {
+        return self.updated;
+    }
+    pub(crate) fn mark(&mut self, generation: u64) {
+        self.generation = generation;
+        self.updated = Some(Instant::now());
+    }
 }
 //--------------------------------------------------------
 pub struct TouchOscRadar {
     values: Vec2,
+    raw: Vec2,            //0..1 as received
+    default: Vec2,        //mapped (radius, rotation)
+    curve: TouchOscCurve, //radius only
     rad_min: f32,
     rad_max: f32,
     rot_min: f32,
     rot_max: f32,
+    unit: TouchOscAngle,
+    wrap: bool,
+    turns: i32,     //seam crossings while wrapping
+    dragging: bool, //raw rotation is from the current drag
+    generation: u64,
+    updated: Option<Instant>,
 }
 impl TouchOscRadar {
     pub fn new(
@@ -574,48 +2382,161 @@ impl TouchOscRadar {
     ) -> Self {
         TouchOscRadar {
             values: pt2(rad_def, rot_def), // (rad,rot)
+            raw: pt2(
+                normalize(rad_def, rad_min, rad_max),
+                normalize(rot_def, rot_min, rot_max),
+            ),
+            default: pt2(rad_def, rot_def),
+            curve: TouchOscCurve::Linear,
             rad_min: rad_min,
             rad_max: rad_max,
             rot_min: rot_min,
             rot_max: rot_max,
+            unit: TouchOscAngle::Radians,
+            wrap: false,
+            turns: 0,
+            dragging: false,
+            generation: 0,
+            updated: None,
         }
     }
     pub fn print(&self, addr: &str, is_verbose: bool) {
         if is_verbose {
-            println!("{} {},{}", addr, self.values.x, self.values.y);
+            debug!(target: "nannou_touchosc::dispatch", addr, x = self.values.x, y = self.values.y);
         }
     }
+    // range changes re-map the current values
     pub fn set_rad_min(&mut self, rad_min: f32) {
         self.rad_min = rad_min;
+        self.values.x = self.rad_range(self.raw.x);
     }
     pub fn set_rad_max(&mut self, rad_max: f32) {
         self.rad_max = rad_max;
+        self.values.x = self.rad_range(self.raw.x);
     }
     pub fn set_rot_min(&mut self, rot_min: f32) {
         self.rot_min = rot_min;
+        self.values.y = self.rotation();
     }
     pub fn set_rot_max(&mut self, rot_max: f32) {
         self.rot_max = rot_max;
+        self.values.y = self.rotation();
     }
     pub fn set_values(&mut self, args: Vec2) {
-        self.values.x = self.rad_range(args.x); //radius
-        self.values.y = self.rot_range(args.y); //rotation
+        self.set_radius(args.x);
+        self.set_rotation(args.y);
+    }
+    pub fn set_radius(&mut self, arg: f32) {
+        self.raw.x = arg;
+        self.values.x = self.rad_range(arg);
+    }
+    pub fn set_rotation(&mut self, arg: f32) {
+        if self.wrap && self.dragging {
+            //a jump of more than half a turn within a drag crossed the seam
+            let delta = arg - self.raw.y;
+            if delta > 0.5 {
+                self.turns -= 1;
+            } else if delta < -0.5 {
+                self.turns += 1;
+            }
+        }
+        self.raw.y = arg;
+        self.values.y = self.rotation();
+        self.dragging = true;
+    }
+    // a new touch jumps to the touched angle, the jump doesn't count as a seam crossing
+    pub fn start_drag(&mut self) {
+        self.dragging = false;
+    }
+    // while wrapping, the rotation keeps counting past the ends of its range
+    // instead of jumping back when the device crosses the 0/1 seam
+    pub fn set_wrap(&mut self, wrap: bool) {
+        self.wrap = wrap;
+        self.turns = 0;
+        self.dragging = false;
+        self.values.y = self.rotation();
+    }
+    pub fn set_angle_unit(&mut self, unit: TouchOscAngle) {
+        self.unit = unit;
+    }
+    // curve changes re-map the current radius
+    pub fn set_curve(&mut self, curve: TouchOscCurve) {
+        self.curve = curve;
+        self.values.x = self.rad_range(self.raw.x);
+    }
+    // (radius, rotation)
+    pub fn set_default(&mut self, default: Vec2) {
+        self.default = default;
     }
     pub fn rad_range(&self, arg: f32) -> f32 {
-        return map_range(arg, 0.0, 1.0, self.rad_min, self.rad_max);
+        return map_range(self.curve.apply(arg), 0.0, 1.0, self.rad_min, self.rad_max);
     }
     pub fn rot_range(&self, arg: f32) -> f32 {
         return map_range(arg, 0.0, 1.0, self.rot_min, self.rot_max);
     }
+    // unwrapped rotation, in units of the rotation range
+    fn rotation(&self) -> f32 {
+        return self.rot_range(self.raw.y + self.turns as f32);
+    }
     pub fn values(&self) -> Vec2 {
         return self.values;
     }
+    pub fn polar(&self) -> Polar {
+        return Polar::new(self.values.x, self.unit.to_radians(self.values.y));
+    }
+    pub fn angle_unit(&self) -> TouchOscAngle {
+        return self.unit;
+    }
+    pub fn wrap(&self) -> bool {
+        return self.wrap;
+    }
+    pub fn raw(&self) -> Vec2 {
+        return self.raw;
+    }
+    pub fn default(&self) -> Vec2 {
+        return self.default;
+    }
+    pub fn raw_default(&self) -> Vec2 {
+        return pt2(
+            self.curve
+                .invert(normalize(self.default.x, self.rad_min, self.rad_max)),
+            normalize(self.default.y, self.rot_min, self.rot_max),
+        );
+    }
+    // returns true if the values changed, a wrapped rotation is unwound
+    pub fn reset(&mut self) -> bool {
+        let before = self.values;
+        self.turns = 0;
+        self.dragging = false;
+        self.raw = self.raw_default();
+        self.values = pt2(self.rad_range(self.raw.x), self.rotation());
+        return self.values != before;
+    }
+    pub fn curve(&self) -> TouchOscCurve {
+        return self.curve;
+    }
+    // generation of the client update in which the value last changed
+    pub fn generation(&self) -> u64 {
+        return self.generation;
+    }
+    pub fn updated(&self) -> Option<Instant> {
+        return self.updated;
+    }
+    pub(crate) fn mark(&mut self, generation: u64) {
+        self.generation = generation;
+        self.updated = Some(Instant::now());
+    }
 }
 //--------------------------------------------------------
 pub struct TouchOscRadial {
     min: f32,
     max: f32,
     value: f32,
+    raw: f32,     //0..1 as received
+    default: f32, //mapped
+    curve: TouchOscCurve,
+    generation: u64,
+    updated: Option<Instant>,
 }
 impl TouchOscRadial {
     pub fn new(min: f32, max: f32, default: f32) -> Self {
@@ -623,49 +2544,135 @@ impl TouchOscRadial {
             min: min,
             max: max,
             value: default,
+            raw: normalize(default, min, max),
+            default,
+            curve: TouchOscCurve::Linear,
+            generation: 0,
+            updated: None,
         }
     }
     pub fn print(&self, addr: &str, is_verbose: bool) {
         if is_verbose {
-            println!("{} {}", addr, self.value);
+            debug!(target: "nannou_touchosc::dispatch", addr, value = self.value);
         }
     }
+    // range changes re-map the current value
     pub fn set_min(&mut self, min: f32) {
         self.min = min;
+        self.value = self.range(self.raw);
     }
     pub fn set_max(&mut self, max: f32) {
         self.max = max;
+        self.value = self.range(self.raw);
     }
     pub fn set_value(&mut self, value: f32) {
+        self.raw = value;
         self.value = self.range(value);
     }
+    // curve changes re-map the current value
+    pub fn set_curve(&mut self, curve: TouchOscCurve) {
+        self.curve = curve;
+        self.value = self.range(self.raw);
+    }
+    pub fn set_default(&mut self, default: f32) {
+        self.default = default;
+    }
     pub fn range(&self, arg: f32) -> f32 {
-        return map_range(arg, 0.0, 1.0, self.min, self.max);
+        return map_range(self.curve.apply(arg), 0.0, 1.0, self.min, self.max);
+    }
+    // inverse of range()
+    pub fn unmap(&self, value: f32) -> f32 {
+        return self.curve.invert(normalize(value, self.min, self.max));
     }
     pub fn value(&self) -> f32 {
         // get
         return self.value;
     }
+    pub fn raw(&self) -> f32 {
+        return self.raw;
+    }
+    pub fn default(&self) -> f32 {
+        return self.default;
+    }
+    pub fn raw_default(&self) -> f32 {
+        return self.unmap(self.default);
+    }
+    // returns true if the value changed
+    pub fn reset(&mut self) -> bool {
+        let before = self.value;
+        self.set_value(self.raw_default());
+        return self.value != before;
+    }
+    pub fn curve(&self) -> TouchOscCurve {
+        return self.curve;
+    }
+    // generation of the client update in which the value last changed
+    pub fn generation(&self) -> u64 {
+        return self.generation;
+    }
+    pub fn updated(&self) -> Option<Instant> {
+        return self.updated;
+    }
+    pub(crate) fn mark(&mut self, generation: u64) {
+        self.generation = generation;
+        self.updated = Some(Instant::now());
+    }
 }
 //--------------------------------------------------------
 pub struct TouchOscRadio {
     size: usize,
     value: i32,
+    default: i32,
+    generation: u64,
+    updated: Option<Instant>,
 }
 impl TouchOscRadio {
     pub fn new(size: usize, default: i32) -> Self {
         TouchOscRadio {
             size: size,
             value: default,
+            default,
+            generation: 0,
+            updated: None,
         }
     }
     pub fn print(&self, addr: &str, is_verbose: bool) {
         if is_verbose {
-            println!("{} {}", addr, self.value);
+            debug!(target: "nannou_touchosc::dispatch", addr, value = self.value);
         }
     }
     pub fn set_value(&mut self, value: i32) {
-        self.value = value;
+        if value >= 0 && (value as usize) < self.size {
+            self.value = value;
+        } else {
+            warn!(
+                target: "nannou_touchosc::dispatch",
+                value,
+                size = self.size,
+                "out of bounds radio index"
+            );
+        }
+    }
+    pub fn set_default(&mut self, default: i32) {
+        if default >= 0 && (default as usize) < self.size {
+            self.default = default;
+        } else {
+            warn!(
+                target: "nannou_touchosc::dispatch",
+                default,
+                size = self.size,
+                "out of bounds radio default"
+            );
+        }
+    }
+    pub fn default(&self) -> i32 {
+        return self.default;
+    }
+    // returns true if the index changed
+    pub fn reset(&mut self) -> bool {
+        let before = self.value;
+        self.value = self.default;
+        return self.value != before;
     }
     pub fn size(&self) -> usize {
         return self.size;
@@ -673,24 +2680,291 @@ impl TouchOscRadio {
     pub fn value(&self) -> i32 {
         return self.value;
     }
+    // index as a 0..1 position
+    pub fn raw(&self) -> f32 {
+        return normalize(self.value as f32, 0.0, self.size as f32 - 1.0);
+    }
+    // generation of the client update in which the value last changed
+    pub fn generation(&self) -> u64 {
+        return self.generation;
+    }
+    pub fn updated(&self) -> Option<Instant> {
+        return self.updated;
+    }
+    pub(crate) fn mark(&mut self, generation: u64) {
+        self.generation = generation;
+        self.updated = Some(Instant::now());
+    }
+}
+//--------------------------------------------------------
+// implemented by enums whose variants map onto the segments of a radio input
+// e.g. `const VARIANTS: &'static [Self] = &[Mode::A, Mode::B];`
+pub trait TouchOscRadioEnum: Copy + 'static {
+    const VARIANTS: &'static [Self];
 }
 //--------------------------------------------------------
 pub struct TouchOscXY {
-    min: f32,
-    max: f32,
+    x: TouchOscAxis,
+    y: TouchOscAxis,
     values: Vec2,
+    raw: Vec2, //0..1 as received
+    generation: u64,
+    updated: Option<Instant>,
 }
 impl TouchOscXY {
     pub fn new(min: f32, max: f32, default: f32) -> Self {
+        return TouchOscXY::with_axes((min, max, default), (min, max, default));
+    }
+    // (min, max, default) per axis
+    pub fn with_axes(x: (f32, f32, f32), y: (f32, f32, f32)) -> Self {
+        let x = TouchOscAxis::new(x.0, x.1, x.2);
+        let y = TouchOscAxis::new(y.0, y.1, y.2);
         TouchOscXY {
+            values: pt2(x.default, y.default), //xy
+            raw: pt2(x.unmap(x.default), y.unmap(y.default)),
+            x,
+            y,
+            generation: 0,
+            updated: None,
+        }
+    }
+    pub fn print(&self, addr: &str, is_verbose: bool) {
+        if is_verbose {
+            debug!(target: "nannou_touchosc::dispatch", addr, x = self.values.x, y = self.values.y);
+        }
+    }
+    // range changes re-map the current values
+    pub fn set_min(&mut self, min: f32) {
+        self.x.min = min;
+        self.y.min = min;
+        self.set_values(self.raw);
+    }
+    pub fn set_max(&mut self, max: f32) {
+        self.x.max = max;
+        self.y.max = max;
+        self.set_values(self.raw);
+    }
+    pub fn set_x_range(&mut self, min: f32, max: f32) {
+        self.x.min = min;
+        self.x.max = max;
+        self.set_values(self.raw);
+    }
+    pub fn set_y_range(&mut self, min: f32, max: f32) {
+        self.y.min = min;
+        self.y.max = max;
+        self.set_values(self.raw);
+    }
+    // x from left to right, y from bottom to top, e.g. app.window_rect()
+    #[cfg(feature = "nannou")]
+    pub fn set_rect(&mut self, rect: nannou::geom::Rect) {
+        self.set_x_range(rect.left(), rect.right());
+        self.set_y_range(rect.bottom(), rect.top());
+    }
+    pub fn set_values(&mut self, args: Vec2) {
+        self.raw = args;
+        self.values = self.range(args);
+    }
+    pub fn set_x(&mut self, arg: f32) {
+        self.raw.x = arg;
+        self.values.x = self.x.range(arg);
+    }
+    pub fn set_y(&mut self, arg: f32) {
+        self.raw.y = arg;
+        self.values.y = self.y.range(arg);
+    }
+    // curve and inversion changes re-map the current values
+    pub fn set_curve(&mut self, curve: TouchOscCurve) {
+        self.x.curve = curve;
+        self.y.curve = curve;
+        self.set_values(self.raw);
+    }
+    pub fn set_curves(&mut self, x: TouchOscCurve, y: TouchOscCurve) {
+        self.x.curve = x;
+        self.y.curve = y;
+        self.set_values(self.raw);
+    }
+    pub fn set_inverted(&mut self, x: bool, y: bool) {
+        self.x.inverted = x;
+        self.y.inverted = y;
+        self.set_values(self.raw);
+    }
+    pub fn set_default(&mut self, default: Vec2) {
+        self.x.default = default.x;
+        self.y.default = default.y;
+    }
+    pub fn range(&self, args: Vec2) -> Vec2 {
+        return pt2(self.x.range(args.x), self.y.range(args.y));
+    }
+    // inverse of range()
+    pub fn unmap(&self, values: Vec2) -> Vec2 {
+        return pt2(self.x.unmap(values.x), self.y.unmap(values.y));
+    }
+    pub fn values(&self) -> Vec2 {
+        return self.values;
+    }
+    pub fn raw(&self) -> Vec2 {
+        return self.raw;
+    }
+    pub fn min(&self) -> Vec2 {
+        return pt2(self.x.min, self.y.min);
+    }
+    pub fn max(&self) -> Vec2 {
+        return pt2(self.x.max, self.y.max);
+    }
+    pub fn default(&self) -> Vec2 {
+        return pt2(self.x.default, self.y.default);
+    }
+    pub fn raw_default(&self) -> Vec2 {
+        return self.unmap(self.default());
+    }
+    // returns true if the values changed
+    pub fn reset(&mut self) -> bool {
+        let before = self.values;
+        self.set_values(self.raw_default());
+        return self.values != before;
+    }
+    pub fn curves(&self) -> (TouchOscCurve, TouchOscCurve) {
+        return (self.x.curve, self.y.curve);
+    }
+    pub fn inverted(&self) -> (bool, bool) {
+        return (self.x.inverted, self.y.inverted);
+    }
+    // generation of the client update in which the value last changed
+    pub fn generation(&self) -> u64 {
+        return self.generation;
+    }
+    pub fn updated(&self) -> Option<Instant> {
+        return self.updated;
+    }
+    pub(crate) fn mark(&mut self, generation: u64) {
+        self.generation = generation;
+        self.updated = Some(Instant::now());
+    }
+}
+// one axis of an xy pad
+struct TouchOscAxis {
+    min: f32,
+    max: f32,
+    default: f32, //mapped
+    curve: TouchOscCurve,
+    inverted: bool,
+}
+impl TouchOscAxis {
+    fn new(min: f32, max: f32, default: f32) -> Self {
+        TouchOscAxis {
             min: min,
             max: max,
-            values: pt2(default, default), //xy
+            default,
+            curve: TouchOscCurve::Linear,
+            inverted: false,
+        }
+    }
+    fn range(&self, arg: f32) -> f32 {
+        let arg = if self.inverted { 1.0 - arg } else { arg };
+        return map_range(self.curve.apply(arg), 0.0, 1.0, self.min, self.max);
+    }
+    fn unmap(&self, value: f32) -> f32 {
+        let arg = self.curve.invert(normalize(value, self.min, self.max));
+        return if self.inverted { 1.0 - arg } else { arg };
+    }
+}
+//--------------------------------------------------------
+pub struct TouchOscText {
+    value: String,
+    default: String,
+    generation: u64,
+    updated: Option<Instant>,
+}
+impl TouchOscText {
+    pub fn new(default: &str) -> Self {
+        TouchOscText {
+            value: default.to_string(),
+            default: default.to_string(),
+            generation: 0,
+            updated: None,
         }
     }
     pub fn print(&self, addr: &str, is_verbose: bool) {
         if is_verbose {
-            println!("{} {},{}", addr, self.values.x, self.values.y);
+            debug!(target: "nannou_touchosc::dispatch", addr, value = %self.value);
+        }
+    }
+    pub fn set_value(&mut self, value: &str) {
+        self.value = value.to_string();
+    }
+    pub fn value(&self) -> &str {
+        return &self.value;
+    }
+    pub fn set_default(&mut self, default: &str) {
+        self.default = default.to_string();
+    }
+    pub fn default(&self) -> &str {
+        return &self.default;
+    }
+    // returns true if the text changed
+    pub fn reset(&mut self) -> bool {
+        if self.value == self.default {
+            return false;
+        }
+        self.value = self.default.clone();
+        return true;
+    }
+    // generation of the client update in which the value last changed
+    pub fn generation(&self) -> u64 {
+        return self.generation;
+    }
+    pub fn updated(&self) -> Option<Instant> {
+        return self.updated;
+    }
+    pub(crate) fn mark(&mut self, generation: u64) {
+        self.generation = generation;
+        self.updated = Some(Instant::now());
+    }
+}
+//--------------------------------------------------------
+// output, shows text sent by the sketch
+pub struct TouchOscLabel {
+    text: String,
+}
+impl TouchOscLabel {
+    pub fn new(text: &str) -> Self {
+        TouchOscLabel {
+            text: text.to_string(),
+        }
+    }
+    // returns true if the text changed
+    pub fn set_text(&mut self, text: &str) -> bool {
+        if self.text == text {
+            return false;
+        }
+        self.text = text.to_string();
+        return true;
+    }
+    pub fn text(&self) -> &str {
+        return &self.text;
+    }
+}
+//--------------------------------------------------------
+pub const DEFAULT_METER_RATE: f32 = 30.0; //sends per second
+
+// output, shows a value sent by the sketch, e.g. a fader used as a level meter
+pub struct TouchOscMeter {
+    min: f32,
+    max: f32,
+    value: f32,
+    interval: Duration,
+    sent: Option<f32>, //last sent 0..1 value
+    last_sent: Option<Instant>,
+}
+impl TouchOscMeter {
+    pub fn new(min: f32, max: f32) -> Self {
+        TouchOscMeter {
+            min,
+            max,
+            value: min,
+            interval: Duration::from_secs_f32(1.0 / DEFAULT_METER_RATE),
+            sent: None,
+            last_sent: None,
         }
     }
     pub fn set_min(&mut self, min: f32) {
@@ -699,14 +2973,572 @@ impl TouchOscXY {
     pub fn set_max(&mut self, max: f32) {
         self.max = max;
     }
-    pub fn set_values(&mut self, args: Vec2) {
-        self.values.x = self.range(args.x);
-        self.values.y = self.range(args.y);
+    // sends per second, 0 or less sends every change
+    pub fn set_rate(&mut self, rate: f32) {
+        self.interval = match rate > 0.0 {
+            true => Duration::from_secs_f32(1.0 / rate),
+            false => Duration::ZERO,
+        };
     }
-    pub fn range(&self, arg: f32) -> f32 {
-        return map_range(arg, 0.0, 1.0, self.min, self.max);
+    pub fn set_value(&mut self, value: f32) {
+        self.value = value;
     }
-    pub fn values(&self) -> Vec2 {
-        return self.values;
+    pub fn value(&self) -> f32 {
+        return self.value;
+    }
+    // value mapped to 0..1, clamped
+    pub fn range(&self) -> f32 {
+        return map_range(self.value, self.min, self.max, 0.0, 1.0).clamp(0.0, 1.0);
+    }
+    // the 0..1 value to send now, if it changed and the interval has passed
+    fn take_due(&mut self, now: Instant) -> Option<f32> {
+        let value = self.range();
+        if self.sent == Some(value) {
+            return None;
+        }
+        if let Some(last_sent) = self.last_sent {
+            if now.duration_since(last_sent) < self.interval {
+                return None;
+            }
+        }
+        self.sent = Some(value);
+        self.last_sent = Some(now);
+        return Some(value);
+    }
+}
+//--------------------------------------------------------
+// response curve between the device's 0..1 position and the mapped range
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TouchOscCurve {
+    #[default]
+    Linear,
+    Power(f32),       // x^n, n > 1 gives finer control near min
+    Exponential(f32), // (e^kx - 1) / (e^k - 1), e.g. for frequencies
+}
+impl TouchOscCurve {
+    pub fn apply(&self, x: f32) -> f32 {
+        return match *self {
+            TouchOscCurve::Power(n) if n > 0.0 => x.max(0.0).powf(n),
+            TouchOscCurve::Exponential(k) if k != 0.0 => (k * x).exp_m1() / k.exp_m1(),
+            _ => x,
+        };
+    }
+    pub fn invert(&self, y: f32) -> f32 {
+        return match *self {
+            TouchOscCurve::Power(n) if n > 0.0 => y.max(0.0).powf(1.0 / n),
+            TouchOscCurve::Exponential(k) if k != 0.0 => (y * k.exp_m1()).ln_1p() / k,
+            _ => y,
+        };
+    }
+}
+//--------------------------------------------------------
+#[derive(Debug, Clone, PartialEq)]
+pub enum TouchOscValue {
+    Bool(bool),   // button
+    Float(f32),   // fader, encoder, radial, grid element
+    Int(i32),     // radio
+    Vec2(Vec2),   // xy, radar
+    Text(String), // text
+}
+impl From<bool> for TouchOscValue {
+    fn from(value: bool) -> Self {
+        TouchOscValue::Bool(value)
+    }
+}
+impl From<f32> for TouchOscValue {
+    fn from(value: f32) -> Self {
+        TouchOscValue::Float(value)
+    }
+}
+impl From<i32> for TouchOscValue {
+    fn from(value: i32) -> Self {
+        TouchOscValue::Int(value)
+    }
+}
+impl From<Vec2> for TouchOscValue {
+    fn from(value: Vec2) -> Self {
+        TouchOscValue::Vec2(value)
+    }
+}
+#[cfg(feature = "nannou")]
+impl From<nannou::glam::Vec2> for TouchOscValue {
+    fn from(value: nannou::glam::Vec2) -> Self {
+        TouchOscValue::Vec2(value.into())
+    }
+}
+impl From<&str> for TouchOscValue {
+    fn from(value: &str) -> Self {
+        TouchOscValue::Text(value.to_string())
+    }
+}
+//--------------------------------------------------------
+#[derive(Debug, Clone)]
+pub struct TouchOscUnknownAddr {
+    pub addr: String,
+    pub types: String, // type tags of the last message, e.g. "ff"
+    pub count: usize,
+    pub last_value: Vec<osc::Type>,
+}
+struct TouchOscLearn {
+    addr: String,
+    input_type: Option<TouchOscInputType>, // None: infer from the first message
+}
+#[derive(Debug, Clone)]
+pub struct TouchOscTypeError {
+    pub addr: String,
+    pub input_type: TouchOscInputType,
+    pub types: String, // type tags of the last rejected message
+    pub count: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // applies a single message as one update() would
+    pub(crate) fn send(client: &mut TouchOscClient, addr: &str, args: Vec<osc::Type>) {
+        client.generation += 1;
+        client.apply_message(osc::Message {
+            addr: addr.to_string(),
+            args: Some(args),
+        });
+    }
+
+    #[test]
+    fn coerce_arguments() {
+        assert_eq!(coerce_f32(&osc::Type::Int(2)), Some(2.0));
+        assert_eq!(coerce_f32(&osc::Type::Double(0.5)), Some(0.5));
+        assert_eq!(coerce_f32(&osc::Type::Bool(true)), Some(1.0));
+        assert_eq!(
+            coerce_f32(&osc::Type::String(" 0.25 ".to_string())),
+            Some(0.25)
+        );
+        assert_eq!(coerce_f32(&osc::Type::String("loud".to_string())), None);
+        assert_eq!(coerce_f32(&osc::Type::Nil), None);
+
+        assert_eq!(coerce_i32(&osc::Type::Float(1.6)), Some(2));
+        assert_eq!(coerce_i32(&osc::Type::String("2.4".to_string())), Some(2));
+        assert_eq!(coerce_i32(&osc::Type::Long(i64::MAX)), None);
+
+        assert_eq!(coerce_string(&osc::Type::Int(3)), Some("3".to_string()));
+        assert_eq!(coerce_string(&osc::Type::Char('a')), Some("a".to_string()));
+        assert_eq!(coerce_string(&osc::Type::Blob(Vec::new())), None);
+    }
+
+    #[test]
+    fn coerced_messages() {
+        let mut client = TouchOscClient::unbound();
+        client.add_button("/button", false);
+        client.add_fader("/fader", 0.0, 10.0, 0.0);
+        client.add_radio("/radio", 4, 0);
+        client.add_xy("/xy", 0.0, 1.0, 0.0);
+
+        send(&mut client, "/button", vec![osc::Type::Bool(true)]);
+        send(&mut client, "/fader", vec![osc::Type::Int(1)]);
+        send(&mut client, "/radio", vec![osc::Type::Float(2.0)]);
+        send(
+            &mut client,
+            "/xy",
+            vec![osc::Type::Int(1), osc::Type::Double(0.5)],
+        );
+        assert!(client.button("/button"));
+        assert_eq!(client.fader("/fader"), 10.0);
+        assert_eq!(client.radio("/radio"), 2);
+        assert_eq!(client.xy("/xy"), pt2(1.0, 0.5));
+
+        //split xy messages
+        send(&mut client, "/xy/y", vec![osc::Type::Float(0.25)]);
+        assert_eq!(client.xy("/xy"), pt2(1.0, 0.25));
+        assert!(client.type_errors().is_empty());
+    }
+
+    #[test]
+    fn strict_type_errors() {
+        let mut client = TouchOscClient::unbound();
+        client.strict();
+        client.add_fader("/fader", 0.0, 1.0, 0.0);
+        client.add_radio("/radio", 4, 1);
+
+        send(&mut client, "/fader", vec![osc::Type::Int(1)]);
+        send(&mut client, "/fader", vec![osc::Type::Int(1)]);
+        send(&mut client, "/radio", vec![osc::Type::Float(2.0)]);
+        assert_eq!(client.fader("/fader"), 0.0);
+        assert_eq!(client.radio("/radio"), 1);
+
+        let errors = client.type_errors();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].addr, "/fader");
+        assert_eq!(errors[0].input_type, TouchOscInputType::Fader);
+        assert_eq!(errors[0].types, "i");
+        assert_eq!(errors[0].count, 2);
+        assert_eq!(errors[1].addr, "/radio");
+        assert_eq!(errors[1].types, "f");
+
+        //native types are still accepted
+        send(&mut client, "/fader", vec![osc::Type::Float(0.5)]);
+        assert_eq!(client.fader("/fader"), 0.5);
+    }
+
+    #[test]
+    fn curve_symmetry() {
+        let curves = [
+            TouchOscCurve::Linear,
+            TouchOscCurve::Power(2.0),
+            TouchOscCurve::Power(0.5),
+            TouchOscCurve::Exponential(4.0),
+            TouchOscCurve::Exponential(-3.0),
+            //degenerate curves behave as linear
+            TouchOscCurve::Power(0.0),
+            TouchOscCurve::Exponential(0.0),
+        ];
+        for curve in curves {
+            assert!(curve.apply(0.0).abs() < 1e-6, "{:?}", curve);
+            assert!((curve.apply(1.0) - 1.0).abs() < 1e-6, "{:?}", curve);
+            for i in 0..=20 {
+                let x = i as f32 / 20.0;
+                let y = curve.apply(x);
+                assert!((curve.invert(y) - x).abs() < 1e-4, "{:?} at {}", curve, x);
+            }
+        }
+        assert_eq!(TouchOscCurve::Power(2.0).apply(0.5), 0.25);
+        assert_eq!(TouchOscCurve::Power(0.0).apply(0.3), 0.3);
+    }
+
+    #[test]
+    fn curve_edits_keep_position() {
+        let mut client = TouchOscClient::unbound();
+        client.add_fader("/fader", 0.0, 10.0, 5.0);
+        client.set_curve("/fader", TouchOscCurve::Power(2.0));
+        assert_eq!(client.fader("/fader"), 2.5);
+        assert_eq!(client.raw("/fader"), Some(TouchOscValue::Float(0.5)));
+        client.set_range("/fader", 0.0, 100.0);
+        assert_eq!(client.fader("/fader"), 25.0);
+    }
+
+    #[test]
+    fn radar_wrap() {
+        let mut client = TouchOscClient::unbound();
+        client.add_radar("/radar", (0.0, 1.0, 0.5), (0.0, 360.0, 0.0));
+        client.wrap_rotation("/radar", true);
+        let rotation = |client: &TouchOscClient| client.radar("/radar").y.round();
+        let arg = |rot: f32| vec![osc::Type::Float(0.5), osc::Type::Float(rot)];
+
+        //the first message is not a seam crossing
+        send(&mut client, "/radar", arg(0.8));
+        assert_eq!(rotation(&client), 288.0);
+
+        //clockwise across the seam
+        send(&mut client, "/radar", arg(0.95));
+        send(&mut client, "/radar", arg(0.05));
+        assert_eq!(rotation(&client), 378.0);
+        send(&mut client, "/radar", arg(0.5));
+        send(&mut client, "/radar", arg(0.95));
+        send(&mut client, "/radar", arg(0.05));
+        assert_eq!(rotation(&client), 738.0);
+
+        //and back
+        send(&mut client, "/radar", arg(0.95));
+        assert_eq!(rotation(&client), 702.0);
+
+        //counter-clockwise below the start of the range
+        client.reset("/radar");
+        send(&mut client, "/radar", arg(0.05));
+        send(&mut client, "/radar", arg(0.95));
+        assert_eq!(rotation(&client), -18.0);
+        send(&mut client, "/radar", arg(0.5));
+        assert_eq!(rotation(&client), -180.0);
+    }
+
+    #[test]
+    fn radar_wrap_fresh_touch() {
+        let mut client = TouchOscClient::unbound();
+        client.add_radar("/radar", (0.0, 1.0, 0.5), (0.0, 360.0, 0.0));
+        client.wrap_rotation("/radar", true);
+        let rotation = |client: &TouchOscClient| client.radar("/radar").y.round();
+        let arg = |rot: f32| vec![osc::Type::Float(0.5), osc::Type::Float(rot)];
+
+        send(&mut client, "/radar", arg(0.1));
+        send(&mut client, "/radar/z", vec![osc::Type::Bool(false)]);
+        //a new touch jumps to the touched angle
+        send(&mut client, "/radar/z", vec![osc::Type::Bool(true)]);
+        send(&mut client, "/radar", arg(0.8));
+        assert_eq!(rotation(&client), 288.0);
+        send(&mut client, "/radar/touch", vec![osc::Type::Int(1)]);
+        send(&mut client, "/radar", arg(0.2));
+        assert_eq!(rotation(&client), 72.0);
+
+        //within a drag the crossing still counts
+        send(&mut client, "/radar", arg(0.9));
+        assert_eq!(rotation(&client), -36.0);
+
+        //reset unwinds and starts over
+        client.reset("/radar");
+        assert_eq!(rotation(&client), 0.0);
+        send(&mut client, "/radar", arg(0.75));
+        assert_eq!(rotation(&client), 270.0);
+    }
+
+    #[test]
+    fn reset_after_edits() {
+        let mut client = TouchOscClient::unbound();
+        client.add_fader("/fader", 0.0, 10.0, 2.0);
+        client.add_encoder("/encoder", 0.0, 1.0, 0.5);
+        client.add_grid("/grid", 2, 0.0, 1.0, 0.25);
+        client.add_radar("/radar", (0.0, 1.0, 0.5), (0.0, 360.0, 90.0));
+        client.add_xy_axes("/xy", (0.0, 10.0, 2.0), (0.0, 1.0, 0.5));
+
+        client.set_range("/fader", 0.0, 100.0);
+        client.set_curve("/encoder", TouchOscCurve::Exponential(3.0));
+        client.set_range("/grid", 0.0, 4.0);
+        client.set_radius_range("/radar", 0.0, 2.0);
+        client.set_rotation_range("/radar", 0.0, 180.0);
+        client.set_curve("/radar", TouchOscCurve::Power(2.0));
+        client.invert_xy("/xy", true, false);
+        client.set_xy_curves("/xy", TouchOscCurve::Power(3.0), TouchOscCurve::Linear);
+
+        client.reset_all();
+        let close = |a: f32, b: f32| (a - b).abs() < 1e-4;
+        assert!(close(client.fader("/fader"), 2.0));
+        assert!(close(client.encoder("/encoder"), 0.5));
+        assert!(close(client.grid("/grid/1"), 0.25));
+        assert!(close(client.grid("/grid/2"), 0.25));
+        let radar = client.radar("/radar");
+        assert!(close(radar.x, 0.5) && close(radar.y, 90.0));
+        let xy = client.xy("/xy");
+        assert!(close(xy.x, 2.0) && close(xy.y, 0.5));
+    }
+
+    #[test]
+    fn double_tap_reset() {
+        let mut client = TouchOscClient::unbound();
+        client.add_fader("/fader", 0.0, 10.0, 2.0);
+        client.add_grid("/grid", 2, 0.0, 1.0, 0.25);
+        client.reset_on_double_tap("/fader");
+        client.reset_on_double_tap("/grid");
+        let touch = |client: &mut TouchOscClient, addr: &str, down: f32| {
+            send(client, addr, vec![osc::Type::Float(down)]);
+        };
+
+        send(&mut client, "/fader", vec![osc::Type::Float(0.8)]);
+        touch(&mut client, "/fader/touch", 1.0);
+        touch(&mut client, "/fader/touch", 0.0);
+        assert_eq!(client.fader("/fader"), 8.0);
+        touch(&mut client, "/fader/touch", 1.0);
+        assert_eq!(client.fader("/fader"), 2.0);
+
+        //values sent while the finger stays down would undo the reset
+        send(&mut client, "/fader", vec![osc::Type::Float(0.9)]);
+        assert_eq!(client.fader("/fader"), 2.0);
+        touch(&mut client, "/fader/touch", 0.0);
+        send(&mut client, "/fader", vec![osc::Type::Float(0.9)]);
+        assert_eq!(client.fader("/fader"), 9.0);
+
+        //grid elements reset on their own, Mk1 "/z" touches count too
+        send(&mut client, "/grid/1", vec![osc::Type::Float(1.0)]);
+        send(&mut client, "/grid/2", vec![osc::Type::Float(1.0)]);
+        touch(&mut client, "/grid/1/z", 1.0);
+        touch(&mut client, "/grid/1/z", 0.0);
+        touch(&mut client, "/grid/1/z", 1.0);
+        assert_eq!(client.grid("/grid/1"), 0.25);
+        assert_eq!(client.grid("/grid/2"), 1.0);
+    }
+
+    #[test]
+    fn reset_to_set_default() {
+        let mut client = TouchOscClient::unbound();
+        client.add_fader("/fader", 0.0, 10.0, 2.0);
+        client.add_grid("/grid", 2, 0.0, 1.0, 0.0);
+        client.add_radio("/radio", 4, 0);
+        client.add_text("/text", "hello");
+
+        client.set_default("/fader", 4.0f32);
+        client.set_range("/fader", 0.0, 8.0);
+        client.set_default("/grid/2", 0.75f32);
+        client.set_default("/radio", 3);
+        send(
+            &mut client,
+            "/text",
+            vec![osc::Type::String("bye".to_string())],
+        );
+
+        client.reset("/fader");
+        client.reset("/grid");
+        client.reset_prefix("/radio");
+        assert_eq!(client.fader("/fader"), 4.0);
+        assert_eq!(client.raw("/fader"), Some(TouchOscValue::Float(0.5)));
+        assert_eq!(client.grid("/grid/1"), 0.0);
+        assert_eq!(client.grid("/grid/2"), 0.75);
+        assert_eq!(client.radio("/radio"), 3);
+        assert_eq!(client.text("/text"), "bye");
+        client.reset("/text");
+        assert_eq!(client.text("/text"), "hello");
+    }
+
+    #[test]
+    fn coalesce_continuous_only() {
+        let mut client = TouchOscClient::unbound();
+        client.add_fader("/fader", 0.0, 1.0, 0.0);
+        client.add_button("/button", false);
+        client.add_xy("/xy", 0.0, 1.0, 0.0);
+        let source: SocketAddr = "127.0.0.1:9000".parse().unwrap();
+        let message = |addr: &str, x: f32| {
+            (
+                osc::Message {
+                    addr: addr.to_string(),
+                    args: Some(vec![osc::Type::Float(x)]),
+                },
+                source,
+            )
+        };
+        let messages = vec![
+            message("/fader", 0.1),
+            message("/button", 1.0),
+            message("/xy/x", 0.2),
+            message("/fader/z", 1.0),
+            message("/fader", 0.3),
+            message("/button", 0.0),
+            message("/xy/x", 0.4),
+            message("/fader/z", 0.0),
+        ];
+        let kept: Vec<String> = client
+            .coalesce_messages(messages)
+            .into_iter()
+            .map(|(msg, _)| msg.addr)
+            .collect();
+        assert_eq!(
+            kept,
+            ["/button", "/fader/z", "/fader", "/button", "/xy/x", "/fader/z"]
+        );
+        assert_eq!(client.collapsed("/fader"), 1);
+        assert_eq!(client.collapsed("/xy/x"), 1);
+        assert_eq!(client.collapsed("/button"), 0);
+        assert_eq!(client.collapsed_total(), 2);
+    }
+
+    #[test]
+    fn learn_binds_unknown_addrs() {
+        let mut client = TouchOscClient::unbound();
+        client.learn_fader("/volume", 0.0, 10.0);
+        client.learn("/title");
+        assert!(client.is_learning());
+
+        //the touch message arrives first and must not be learned
+        send(&mut client, "/1/fader3/touch", vec![osc::Type::Float(1.0)]);
+        send(&mut client, "/1/fader3", vec![osc::Type::Float(0.5)]);
+        assert_eq!(client.learned("/volume"), Some("/1/fader3"));
+        assert_eq!(client.fader("/volume"), 5.0);
+
+        send(
+            &mut client,
+            "/1/label",
+            vec![osc::Type::String("intro".to_string())],
+        );
+        assert_eq!(client.learned("/title"), Some("/1/label"));
+        assert_eq!(client.text("/title"), "intro");
+        assert!(!client.is_learning());
+        let unknown: Vec<&str> = client
+            .unknown_addrs()
+            .iter()
+            .map(|unknown| unknown.addr.as_str())
+            .collect();
+        assert_eq!(unknown, ["/1/fader3/touch"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "address in use")]
+    fn learn_reserves_addr() {
+        let mut client = TouchOscClient::unbound();
+        client.learn("/a");
+        client.learn("/a");
+    }
+
+    #[test]
+    #[should_panic(expected = "address in use")]
+    fn learn_blocks_add() {
+        let mut client = TouchOscClient::unbound();
+        client.learn("/b");
+        client.add_fader("/b", 0.0, 1.0, 0.0);
+    }
+
+    #[test]
+    fn replace_cancels_learn() {
+        let mut client = TouchOscClient::unbound();
+        client.learn("/a");
+        client.learn_fader("/b", 0.0, 1.0);
+        client.replace_fader("/a", 0.0, 1.0, 0.0);
+        client.replace_xy("/b", 0.0, 1.0, 0.0);
+        assert!(!client.is_learning());
+    }
+
+    // every registered address is in exactly one typed map
+    fn assert_consistent(client: &TouchOscClient) {
+        let typed = client.touchosc_buttons.len()
+            + client.touchosc_faders.len()
+            + client.touchosc_grids.len()
+            + client.touchosc_encoders.len()
+            + client.touchosc_radars.len()
+            + client.touchosc_radials.len()
+            + client.touchosc_radios.len()
+            + client.touchosc_xys.len()
+            + client.touchosc_texts.len();
+        assert_eq!(typed, client.lookup_table.len());
+        assert_eq!(
+            client.touchosc_labels.len() + client.touchosc_meters.len(),
+            client.output_table.len()
+        );
+    }
+
+    #[test]
+    fn remove_and_readd() {
+        let mut client = TouchOscClient::unbound();
+        client.add_fader("/scene2/speed", 0.0, 1.0, 0.5);
+        client.add_grid("/scene2/grid", 2, 0.0, 1.0, 0.0);
+        client.add_label("/scene2/title", "two");
+        client.add_radio("/scene20/mode", 3, 0);
+        client.add_button("/toggle", false);
+        assert_consistent(&client);
+
+        //same address, another type
+        assert_eq!(client.remove("/toggle"), Some(TouchOscInputType::Button));
+        assert_eq!(client.remove("/toggle"), None);
+        client.add_xy("/toggle", 0.0, 1.0, 0.0);
+        assert_consistent(&client);
+        assert_eq!(
+            client.value("/toggle"),
+            Some(TouchOscValue::Vec2(pt2(0.0, 0.0)))
+        );
+        client.replace_text("/toggle", "on");
+        assert_consistent(&client);
+        assert_eq!(client.text("/toggle"), "on");
+
+        assert_eq!(client.remove_prefix("/scene2/"), 3);
+        assert_consistent(&client);
+        assert!(client.value("/scene2/speed").is_none());
+        assert_eq!(client.radio("/scene20/mode"), 0);
+        client.add_fader("/scene2/speed", 0.0, 1.0, 0.5);
+        assert_consistent(&client);
+
+        client.clear();
+        assert_consistent(&client);
+        assert!(client.lookup_table.is_empty());
+    }
+
+    #[test]
+    fn remove_drops_routes_and_taps() {
+        let mut client = TouchOscClient::unbound();
+        client.learn_fader("/volume", 0.0, 1.0);
+        send(&mut client, "/1/fader3", vec![osc::Type::Float(0.5)]);
+        client.reset_on_double_tap("/volume");
+        assert_eq!(client.learned("/volume"), Some("/1/fader3"));
+
+        client.remove("/volume");
+        assert_eq!(client.learned("/volume"), None);
+        assert!(!client.resets_on_double_tap("/volume"));
+
+        //the incoming address is unknown again, not routed to a new input
+        client.add_fader("/volume", 0.0, 1.0, 0.0);
+        send(&mut client, "/1/fader3", vec![osc::Type::Float(0.5)]);
+        assert_eq!(client.fader("/volume"), 0.0);
+        assert_eq!(client.unknown_addrs()[0].addr, "/1/fader3");
     }
 }
@@ -0,0 +1,59 @@
+// nannou_touchosc math
+// mikhail mansion
+// https://mikhailmansion.art
+
+// The few bits of nannou's math used by the client, so the core builds
+// without nannou. With the "nannou" feature, Vec2 converts to and from
+// nannou's (glam) Vec2.
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Vec2 {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Vec2 {
+    pub const ZERO: Vec2 = Vec2 { x: 0.0, y: 0.0 };
+
+    pub fn new(x: f32, y: f32) -> Self {
+        Vec2 { x, y }
+    }
+}
+
+impl From<(f32, f32)> for Vec2 {
+    fn from((x, y): (f32, f32)) -> Self {
+        Vec2 { x, y }
+    }
+}
+
+impl From<Vec2> for (f32, f32) {
+    fn from(v: Vec2) -> Self {
+        (v.x, v.y)
+    }
+}
+
+#[cfg(feature = "nannou")]
+impl From<Vec2> for nannou::glam::Vec2 {
+    fn from(v: Vec2) -> Self {
+        nannou::glam::Vec2::new(v.x, v.y)
+    }
+}
+
+#[cfg(feature = "nannou")]
+impl From<nannou::glam::Vec2> for Vec2 {
+    fn from(v: nannou::glam::Vec2) -> Self {
+        Vec2 { x: v.x, y: v.y }
+    }
+}
+
+pub fn pt2(x: f32, y: f32) -> Vec2 {
+    Vec2 { x, y }
+}
+
+// same as nannou's map_range for f32
+pub fn map_range(val: f32, in_min: f32, in_max: f32, out_min: f32, out_max: f32) -> f32 {
+    if (in_min - in_max).abs() < f32::EPSILON {
+        return out_min;
+    }
+    (val - in_min) / (in_max - in_min) * (out_max - out_min) + out_min
+}
@@ -0,0 +1,219 @@
+// nannou_touchosc outgoing queue
+// mikhail mansion
+// https://mikhailmansion.art
+
+// Messages sent to the device are queued and go out together on the next
+// `update()` (or `flush()`). Until then a newer message for the same address
+// replaces the queued one, and at flush time messages repeating the last sent
+// value are skipped, unless the device has reported a value for that address
+// since. The rest is packed into OSC bundles, subject to optional
+// per-address and global rate caps; whatever a cap holds back stays queued.
+
+use crate::{osc, osc_type_tags, TouchOscClient};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+// messages per bundle, keeps bundles well below common UDP payload limits
+pub const MAX_BUNDLE_SIZE: usize = 32;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TouchOscSendStats {
+    pub queued: u64,    // messages passed to send()
+    pub sent: u64,      // messages sent
+    pub bundles: u64,   // packets sent (bundles or single messages)
+    pub coalesced: u64, // replaced by a newer message before being sent
+    pub unchanged: u64, // skipped, same arguments as the last sent message
+    pub deferred: u64,  // held back by a rate cap at a flush, sent later
+    pub failed: u64,    // messages of packets the socket rejected
+}
+
+impl TouchOscSendStats {
+    // messages that were never sent
+    pub fn dropped(&self) -> u64 {
+        return self.coalesced + self.unchanged + self.failed;
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct TouchOscOutgoing {
+    pending: Vec<(String, Vec<osc::Type>)>, //in send order
+    last_sent: HashMap<String, (Vec<osc::Type>, Instant)>,
+    addr_interval: Duration, //zero: no per-address cap
+    rate: f32,               //messages per second, 0: no global cap
+    budget: f32,             //messages the global cap allows right now
+    budget_time: Option<Instant>,
+    stats: TouchOscSendStats,
+}
+
+impl TouchOscOutgoing {
+    fn push(&mut self, addr: &str, args: Vec<osc::Type>) {
+        self.stats.queued += 1;
+        match self.pending.iter_mut().find(|(queued, _)| queued == addr) {
+            Some(queued) => {
+                queued.1 = args;
+                self.stats.coalesced += 1;
+            }
+            None => self.pending.push((addr.to_string(), args)),
+        }
+    }
+    // removes the messages to send now from the queue
+    fn take_due(&mut self, now: Instant) -> Vec<(String, Vec<osc::Type>)> {
+        if self.rate > 0.0 {
+            let elapsed = match self.budget_time {
+                Some(time) => now.duration_since(time).as_secs_f32(),
+                None => 1.0,
+            };
+            //at most one second worth of messages in a burst
+            self.budget = (self.budget + elapsed * self.rate).min(self.rate);
+            self.budget_time = Some(now);
+        }
+
+        let mut due = Vec::new();
+        let mut kept = Vec::new();
+        for (addr, args) in self.pending.drain(..) {
+            if let Some((last_args, last_time)) = self.last_sent.get(&addr) {
+                if *last_args == args {
+                    self.stats.unchanged += 1;
+                    continue;
+                }
+                if now.duration_since(*last_time) < self.addr_interval {
+                    self.stats.deferred += 1;
+                    kept.push((addr, args));
+                    continue;
+                }
+            }
+            if self.rate > 0.0 {
+                if self.budget < 1.0 {
+                    self.stats.deferred += 1;
+                    kept.push((addr, args));
+                    continue;
+                }
+                self.budget -= 1.0;
+            }
+            self.last_sent.insert(addr.clone(), (args.clone(), now));
+            due.push((addr, args));
+        }
+        self.pending = kept;
+        return due;
+    }
+}
+
+impl TouchOscClient {
+    // queues a message to the connected device, nothing is queued before connect()
+    // queued messages go out on the next update() or flush()
+    pub fn send(&mut self, addr: &str, args: Vec<osc::Type>) {
+        if self.osc_sender.is_none() {
+            return;
+        }
+        self.outgoing.push(addr, args);
+    }
+    // sends queued messages that are due, in bundles of up to MAX_BUNDLE_SIZE
+    pub fn flush(&mut self) {
+        let sender = match &self.osc_sender {
+            Some(sender) => sender,
+            None => return,
+        };
+        let due = self.outgoing.take_due(Instant::now());
+        for chunk in due.chunks(MAX_BUNDLE_SIZE) {
+            if self.verbose {
+                for (addr, args) in chunk {
+                    debug!(target: "nannou_touchosc::send", addr = %addr, types = %osc_type_tags(args));
+                }
+            }
+            let packet = match chunk {
+                [(addr, args)] => osc::Packet::Message(osc::Message {
+                    addr: addr.clone(),
+                    args: Some(args.clone()),
+                }),
+                _ => osc::Packet::Bundle(osc::Bundle {
+                    timetag: osc::Type::Time(0, 1), //immediately
+                    content: chunk
+                        .iter()
+                        .map(|(addr, args)| {
+                            osc::rosc::OscPacket::Message(osc::Message {
+                                addr: addr.clone(),
+                                args: Some(args.clone()),
+                            })
+                        })
+                        .collect(),
+                }),
+            };
+            match sender.send(packet) {
+                Ok(_) => {
+                    self.outgoing.stats.sent += chunk.len() as u64;
+                    self.outgoing.stats.bundles += 1;
+                }
+                Err(err) => {
+                    warn!(
+                        target: "nannou_touchosc::send",
+                        messages = chunk.len(),
+                        error = %err,
+                        "not sent"
+                    );
+                    self.outgoing.stats.failed += chunk.len() as u64;
+                }
+            }
+        }
+    }
+    // number of messages waiting for the next flush
+    pub fn queued(&self) -> usize {
+        return self.outgoing.pending.len();
+    }
+    // sends per second for each address, 0 (the default) for no cap
+    pub fn set_max_rate_per_addr(&mut self, rate: f32) {
+        self.outgoing.addr_interval = match rate > 0.0 {
+            true => Duration::from_secs_f32(1.0 / rate),
+            false => Duration::ZERO,
+        };
+    }
+    // messages per second over all addresses, 0 (the default) for no cap
+    pub fn set_max_rate(&mut self, rate: f32) {
+        self.outgoing.rate = rate.max(0.0);
+        self.outgoing.budget_time = None;
+    }
+    pub fn send_stats(&self) -> TouchOscSendStats {
+        return self.outgoing.stats;
+    }
+    pub fn reset_send_stats(&mut self) {
+        self.outgoing.stats = TouchOscSendStats::default();
+    }
+    // forgets the last sent values, so the next message to every address is sent
+    pub(crate) fn forget_sent(&mut self) {
+        self.outgoing.last_sent.clear();
+    }
+    // forgets the last value sent to addr, once the device reports a newer one
+    // the same value has to be sent again to restore it
+    pub(crate) fn forget_sent_addr(&mut self, addr: &str) {
+        self.outgoing.last_sent.remove(addr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::send;
+
+    #[test]
+    fn resend_after_incoming() {
+        let mut client = TouchOscClient::unbound();
+        client.add_fader("/fader", 0.0, 1.0, 0.5);
+        client.add_xy("/xy", 0.0, 1.0, 0.5);
+        let now = Instant::now();
+        let value = vec![osc::Type::Float(0.5)];
+
+        client.outgoing.push("/fader", value.clone());
+        client.outgoing.push("/xy", value.clone());
+        assert_eq!(client.outgoing.take_due(now).len(), 2);
+        client.outgoing.push("/fader", value.clone());
+        assert!(client.outgoing.take_due(now).is_empty());
+
+        //the device moved away from the sent value, sending it again restores it
+        send(&mut client, "/fader", vec![osc::Type::Float(0.9)]);
+        send(&mut client, "/xy/x", vec![osc::Type::Float(0.9)]);
+        client.outgoing.push("/fader", value.clone());
+        client.outgoing.push("/xy", value.clone());
+        assert_eq!(client.outgoing.take_due(now).len(), 2);
+        assert_eq!(client.send_stats().unchanged, 1);
+    }
+}
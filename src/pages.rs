@@ -0,0 +1,333 @@
+// nannou_touchosc pages
+// mikhail mansion
+// https://mikhailmansion.art
+
+// Tracks the page a TouchOSC pager is showing. Inputs are assigned to named
+// page groups by address prefix, so a sketch can tell which part of the
+// controller is visible, skip work for hidden pages and react to page changes.
+//
+//     touchosc.add_pager("/pager1", &["mix", "fx"]);
+//     touchosc.assign_page("/mix", "mix");
+//     touchosc.assign_page("/fx", "fx");
+//     touchosc.on_page_change(|change| println!("now on {}", change.to));
+//
+//     if touchosc.page_active("fx") { /* ... */ }
+
+use crate::{coerce_i32, in_namespace, osc, osc_type_tags, TouchOscClient};
+use std::collections::HashMap;
+use tracing::{info, warn};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TouchOscPageChange {
+    pub pager: String,
+    pub from: Option<String>,
+    pub to: String,
+}
+
+type TouchOscPageHook = Box<dyn FnMut(&TouchOscPageChange) + Send + Sync>;
+
+#[derive(Default)]
+pub(crate) struct TouchOscPages {
+    pagers: HashMap<String, TouchOscPager>,
+    groups: HashMap<String, TouchOscPageGroup>, //page name -> group
+    hooks: Vec<TouchOscPageHook>,
+}
+
+struct TouchOscPager {
+    pages: Vec<String>, //in pager order
+    active: usize,
+}
+
+struct TouchOscPageGroup {
+    prefixes: Vec<String>,
+    enabled: bool,
+}
+
+impl TouchOscPages {
+    pub(crate) fn is_pager(&self, addr: &str) -> bool {
+        return self.pagers.contains_key(addr);
+    }
+    // page group of addr, the group with the longest matching prefix
+    fn group_of(&self, addr: &str) -> Option<(&str, &TouchOscPageGroup)> {
+        let mut best: Option<(&str, &TouchOscPageGroup, usize)> = None;
+        for (page, group) in &self.groups {
+            for prefix in &group.prefixes {
+                if in_namespace(addr, prefix) && best.is_none_or(|(_, _, len)| prefix.len() > len) {
+                    best = Some((page, group, prefix.len()));
+                }
+            }
+        }
+        return best.map(|(page, group, _)| (page, group));
+    }
+    // false for inputs of a disabled page group, their messages are ignored
+    pub(crate) fn accepts(&self, addr: &str) -> bool {
+        return match self.group_of(addr) {
+            Some((_, group)) => group.enabled,
+            None => true,
+        };
+    }
+    fn visible(&self, page: &str) -> bool {
+        return self
+            .pagers
+            .values()
+            .any(|pager| pager.pages[pager.active] == page);
+    }
+    // switches the pager to index, returns the change if the page changed
+    fn switch(&mut self, addr: &str, index: usize) -> Option<TouchOscPageChange> {
+        let pager = self.pagers.get_mut(addr)?;
+        if index >= pager.pages.len() || index == pager.active {
+            return None;
+        }
+        let from = pager.pages[pager.active].clone();
+        pager.active = index;
+        return Some(TouchOscPageChange {
+            pager: addr.to_string(),
+            from: Some(from),
+            to: pager.pages[index].clone(),
+        });
+    }
+    fn notify(&mut self, change: &TouchOscPageChange) {
+        for hook in &mut self.hooks {
+            hook(change);
+        }
+    }
+}
+
+impl TouchOscClient {
+    // registers a pager sending the index (or name) of the visible page,
+    // pages are listed in pager order, the first page is visible initially
+    pub fn add_pager(&mut self, addr: &str, pages: &[&str]) {
+        self.verify_free_addr(addr);
+        if pages.is_empty() {
+            panic!("\"{}\" pager has no pages!", addr);
+        }
+        self.pages.pagers.insert(
+            addr.to_string(),
+            TouchOscPager {
+                pages: pages.iter().map(|page| page.to_string()).collect(),
+                active: 0,
+            },
+        );
+        let change = TouchOscPageChange {
+            pager: addr.to_string(),
+            from: None,
+            to: pages[0].to_string(),
+        };
+        self.pages.notify(&change);
+    }
+    // assigns the input at prefix and every input below it to a page group
+    pub fn assign_page(&mut self, prefix: &str, page: &str) {
+        let group = self
+            .pages
+            .groups
+            .entry(page.to_string())
+            .or_insert(TouchOscPageGroup {
+                prefixes: Vec::new(),
+                enabled: true,
+            });
+        group
+            .prefixes
+            .push(prefix.trim_end_matches('/').to_string());
+    }
+    // page group of the input at addr, if any
+    pub fn page_of(&self, addr: &str) -> Option<&str> {
+        return self.pages.group_of(addr).map(|(page, _)| page);
+    }
+    // the page the pager at addr shows
+    pub fn active_page(&self, pager: &str) -> Option<&str> {
+        return self
+            .pages
+            .pagers
+            .get(pager)
+            .map(|pager| pager.pages[pager.active].as_str());
+    }
+    // switches a pager from the sketch, e.g. to follow a scene change
+    pub fn set_page(&mut self, pager: &str, page: &str) {
+        let index = match self.pages.pagers.get(pager) {
+            Some(pager) => pager.pages.iter().position(|name| name == page),
+            None => panic!("\"{}\" is not a pager!", pager),
+        };
+        match index {
+            Some(index) => self.switch_page(pager, index),
+            None => warn!(target: "nannou_touchosc::pages", pager, page, "no such page"),
+        }
+    }
+    // true if a pager shows the page
+    pub fn page_visible(&self, page: &str) -> bool {
+        return self.pages.visible(page);
+    }
+    // a disabled page group ignores messages and is never active
+    pub fn set_page_enabled(&mut self, page: &str, enabled: bool) {
+        match self.pages.groups.get_mut(page) {
+            Some(group) => group.enabled = enabled,
+            None => warn!(target: "nannou_touchosc::pages", page, "no such page group"),
+        }
+    }
+    pub fn page_enabled(&self, page: &str) -> bool {
+        return self
+            .pages
+            .groups
+            .get(page)
+            .is_some_and(|group| group.enabled);
+    }
+    // true if the page group is enabled and visible
+    pub fn page_active(&self, page: &str) -> bool {
+        return self.page_enabled(page) && self.page_visible(page);
+    }
+    // true if the input at addr is on an active page or in no page group
+    pub fn input_active(&self, addr: &str) -> bool {
+        return match self.pages.group_of(addr) {
+            Some((page, group)) => group.enabled && self.pages.visible(page),
+            None => true,
+        };
+    }
+    // called on every page change, hooks run on the thread applying messages
+    pub fn on_page_change<F>(&mut self, hook: F)
+    where
+        F: FnMut(&TouchOscPageChange) + Send + Sync + 'static,
+    {
+        self.pages.hooks.push(Box::new(hook));
+    }
+
+    // pager messages carry the page index or the page name
+    pub(crate) fn apply_pager(&mut self, addr: &str, args: &[osc::Type]) {
+        let index = match args.first() {
+            Some(osc::Type::String(name)) => self.pages.pagers[addr]
+                .pages
+                .iter()
+                .position(|page| page == name),
+            Some(arg) => coerce_i32(arg).and_then(|index| usize::try_from(index).ok()),
+            None => None,
+        };
+        let count = self.pages.pagers[addr].pages.len();
+        match index {
+            Some(index) if index < count => self.switch_page(addr, index),
+            Some(index) => warn!(
+                target: "nannou_touchosc::pages",
+                pager = addr,
+                index,
+                pages = count,
+                "page index out of range"
+            ),
+            None => warn!(
+                target: "nannou_touchosc::pages",
+                pager = addr,
+                args = %osc_type_tags(args),
+                "invalid page"
+            ),
+        }
+    }
+    fn switch_page(&mut self, pager: &str, index: usize) {
+        if let Some(change) = self.pages.switch(pager, index) {
+            info!(
+                target: "nannou_touchosc::pages",
+                pager,
+                from = ?change.from,
+                to = %change.to,
+                "page changed"
+            );
+            self.pages.notify(&change);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::send;
+    use std::sync::{Arc, Mutex};
+
+    fn client() -> TouchOscClient {
+        let mut client = TouchOscClient::unbound();
+        client.add_pager("/pager", &["mix", "fx", "fx2"]);
+        client.add_fader("/mix/level", 0.0, 1.0, 0.0);
+        client.add_fader("/fx/level", 0.0, 1.0, 0.0);
+        client.add_fader("/fx/reverb/level", 0.0, 1.0, 0.0);
+        client.add_fader("/fxbus", 0.0, 1.0, 0.0);
+        client.assign_page("/mix", "mix");
+        client.assign_page("/fx", "fx");
+        client.assign_page("/fx/reverb/", "fx2");
+        client
+    }
+
+    #[test]
+    fn pager_index_and_name() {
+        let mut client = client();
+        assert_eq!(client.active_page("/pager"), Some("mix"));
+        send(&mut client, "/pager", vec![osc::Type::Int(1)]);
+        assert_eq!(client.active_page("/pager"), Some("fx"));
+        send(&mut client, "/pager", vec![osc::Type::Float(2.0)]);
+        assert_eq!(client.active_page("/pager"), Some("fx2"));
+        send(
+            &mut client,
+            "/pager",
+            vec![osc::Type::String("mix".to_string())],
+        );
+        assert_eq!(client.active_page("/pager"), Some("mix"));
+
+        //out of range, negative and unknown pages are ignored
+        send(&mut client, "/pager", vec![osc::Type::Int(3)]);
+        send(&mut client, "/pager", vec![osc::Type::Int(-1)]);
+        send(
+            &mut client,
+            "/pager",
+            vec![osc::Type::String("eq".to_string())],
+        );
+        send(&mut client, "/pager", Vec::new());
+        assert_eq!(client.active_page("/pager"), Some("mix"));
+        assert!(client.page_visible("mix") && !client.page_visible("fx"));
+    }
+
+    #[test]
+    fn longest_prefix_group() {
+        let client = client();
+        assert_eq!(client.page_of("/mix/level"), Some("mix"));
+        assert_eq!(client.page_of("/fx/level"), Some("fx"));
+        assert_eq!(client.page_of("/fx/reverb/level"), Some("fx2"));
+        assert_eq!(client.page_of("/fxbus"), None);
+        assert!(client.input_active("/mix/level"));
+        assert!(!client.input_active("/fx/level"));
+        assert!(client.input_active("/fxbus"));
+    }
+
+    #[test]
+    fn disabled_group_ignores_messages() {
+        let mut client = client();
+        client.set_page_enabled("fx", false);
+        send(&mut client, "/fx/level", vec![osc::Type::Float(0.5)]);
+        send(&mut client, "/fx/reverb/level", vec![osc::Type::Float(0.5)]);
+        assert_eq!(client.fader("/fx/level"), 0.0);
+        assert_eq!(client.fader("/fx/reverb/level"), 0.5);
+        assert!(!client.page_enabled("fx") && !client.page_active("fx"));
+
+        client.set_page_enabled("fx", true);
+        send(&mut client, "/fx/level", vec![osc::Type::Float(0.5)]);
+        assert_eq!(client.fader("/fx/level"), 0.5);
+    }
+
+    #[test]
+    fn page_change_hooks() {
+        let mut client = TouchOscClient::unbound();
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let seen = changes.clone();
+        client.on_page_change(move |change| seen.lock().unwrap().push(change.clone()));
+        client.add_pager("/pager", &["mix", "fx"]);
+        send(&mut client, "/pager", vec![osc::Type::Int(1)]);
+        send(&mut client, "/pager", vec![osc::Type::Int(1)]);
+        client.set_page("/pager", "mix");
+
+        let change = |from: Option<&str>, to: &str| TouchOscPageChange {
+            pager: "/pager".to_string(),
+            from: from.map(|from| from.to_string()),
+            to: to.to_string(),
+        };
+        assert_eq!(
+            *changes.lock().unwrap(),
+            vec![
+                change(None, "mix"),
+                change(Some("mix"), "fx"),
+                change(Some("fx"), "mix"),
+            ]
+        );
+    }
+}
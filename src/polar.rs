@@ -0,0 +1,127 @@
+// nannou_touchosc polar coordinates
+// mikhail mansion
+// https://mikhailmansion.art
+
+// A radar's (radius, rotation) as a named type. The angle is always stored in
+// radians, the unit of the radar's rotation range is set per radar with
+// `TouchOscClient::set_angle_unit` and converted when reading `polar()`.
+//
+//     touchosc.add_radar("/radar", (0.0, 100.0, 50.0), (0.0, 360.0, 0.0));
+//     touchosc.set_angle_unit("/radar", TouchOscAngle::Degrees);
+//     let point = touchosc.polar("/radar").to_cartesian();
+
+use crate::math::{pt2, Vec2};
+use std::f32::consts::TAU;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Polar {
+    pub radius: f32,
+    pub angle: f32, // radians, counter-clockwise from +x
+}
+
+impl Polar {
+    pub fn new(radius: f32, angle: f32) -> Self {
+        Polar { radius, angle }
+    }
+    pub fn from_degrees(radius: f32, degrees: f32) -> Self {
+        return Polar::new(radius, degrees.to_radians());
+    }
+    pub fn from_turns(radius: f32, turns: f32) -> Self {
+        return Polar::new(radius, turns * TAU);
+    }
+    pub fn from_cartesian(point: Vec2) -> Self {
+        return Polar::new(point.x.hypot(point.y), point.y.atan2(point.x));
+    }
+
+    pub fn degrees(&self) -> f32 {
+        return self.angle.to_degrees();
+    }
+    pub fn turns(&self) -> f32 {
+        return self.angle / TAU;
+    }
+    pub fn angle_in(&self, unit: TouchOscAngle) -> f32 {
+        return unit.from_radians(self.angle);
+    }
+    pub fn to_cartesian(&self) -> Vec2 {
+        return pt2(
+            self.radius * self.angle.cos(),
+            self.radius * self.angle.sin(),
+        );
+    }
+}
+
+// unit of a radar's rotation range
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TouchOscAngle {
+    #[default]
+    Radians,
+    Degrees,
+    Turns,
+}
+
+impl TouchOscAngle {
+    pub fn to_radians(&self, angle: f32) -> f32 {
+        return match self {
+            TouchOscAngle::Radians => angle,
+            TouchOscAngle::Degrees => angle.to_radians(),
+            TouchOscAngle::Turns => angle * TAU,
+        };
+    }
+    pub fn from_radians(&self, angle: f32) -> f32 {
+        return match self {
+            TouchOscAngle::Radians => angle,
+            TouchOscAngle::Degrees => angle.to_degrees(),
+            TouchOscAngle::Turns => angle / TAU,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::{FRAC_PI_2, PI};
+
+    fn close(a: f32, b: f32) -> bool {
+        (a - b).abs() < 1e-5
+    }
+
+    #[test]
+    fn angle_units() {
+        for unit in [
+            TouchOscAngle::Radians,
+            TouchOscAngle::Degrees,
+            TouchOscAngle::Turns,
+        ] {
+            for angle in [-1.5, 0.0, 0.25, 3.0] {
+                assert!(close(unit.to_radians(unit.from_radians(angle)), angle));
+            }
+        }
+        assert!(close(TouchOscAngle::Degrees.to_radians(180.0), PI));
+        assert!(close(TouchOscAngle::Turns.to_radians(0.25), FRAC_PI_2));
+        assert!(close(TouchOscAngle::Degrees.from_radians(FRAC_PI_2), 90.0));
+        assert_eq!(TouchOscAngle::default(), TouchOscAngle::Radians);
+    }
+
+    #[test]
+    fn polar_angles() {
+        let polar = Polar::from_degrees(2.0, 90.0);
+        assert!(close(polar.angle, FRAC_PI_2));
+        assert!(close(polar.degrees(), 90.0));
+        assert!(close(polar.turns(), 0.25));
+        assert!(close(polar.angle_in(TouchOscAngle::Degrees), 90.0));
+        assert!(close(Polar::from_turns(1.0, 0.5).angle, PI));
+    }
+
+    #[test]
+    fn cartesian() {
+        let point = Polar::new(2.0, FRAC_PI_2).to_cartesian();
+        assert!(close(point.x, 0.0) && close(point.y, 2.0));
+        let point = Polar::from_degrees(1.0, 180.0).to_cartesian();
+        assert!(close(point.x, -1.0) && close(point.y, 0.0));
+
+        let polar = Polar::from_cartesian(pt2(3.0, 4.0));
+        assert!(close(polar.radius, 5.0));
+        let back = polar.to_cartesian();
+        assert!(close(back.x, 3.0) && close(back.y, 4.0));
+    }
+}
@@ -0,0 +1,119 @@
+// nannou_touchosc shared reader
+// mikhail mansion
+// https://mikhailmansion.art
+
+// Thread-safe handle to a client whose OSC receiver runs on its own thread
+// (see `TouchOscClient::spawn_receiver`). Cloning is cheap, every clone reads
+// the same state, so values can be read from the render thread, audio
+// callbacks or other windows.
+
+use crate::{Polar, TouchOscClient, TouchOscRadioEnum, TouchOscSnapshot, Vec2};
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard, Weak};
+use std::time::Instant;
+
+#[derive(Clone)]
+pub struct TouchOscReader {
+    client: Arc<RwLock<TouchOscClient>>,
+}
+
+impl TouchOscReader {
+    pub(crate) fn new(client: TouchOscClient) -> Self {
+        TouchOscReader {
+            client: Arc::new(RwLock::new(client)),
+        }
+    }
+    pub(crate) fn downgrade(&self) -> Weak<RwLock<TouchOscClient>> {
+        return Arc::downgrade(&self.client);
+    }
+
+    // access to the client, e.g. to read several values under one lock
+    // or to register inputs after the receiver thread was spawned
+    pub fn read<R>(&self, f: impl FnOnce(&TouchOscClient) -> R) -> R {
+        return f(&read_lock(&self.client));
+    }
+    pub fn write<R>(&self, f: impl FnOnce(&mut TouchOscClient) -> R) -> R {
+        return f(&mut write_lock(&self.client));
+    }
+
+    // all values under a single lock, consistent for the whole frame
+    pub fn snapshot(&self) -> TouchOscSnapshot {
+        return read_lock(&self.client).snapshot();
+    }
+
+    // change detection, see TouchOscClient::changed_since
+    // the receiver thread bumps the generation once per packet
+
+    pub fn generation(&self) -> u64 {
+        return read_lock(&self.client).generation();
+    }
+    pub fn changed_since(&self, addr: &str, generation: u64) -> bool {
+        return read_lock(&self.client).changed_since(addr, generation);
+    }
+    pub fn updated(&self, addr: &str) -> Option<Instant> {
+        return read_lock(&self.client).updated(addr);
+    }
+
+    // get input values
+
+    pub fn button(&self, addr: &str) -> bool {
+        return read_lock(&self.client).button(addr);
+    }
+    pub fn fader(&self, addr: &str) -> f32 {
+        return read_lock(&self.client).fader(addr);
+    }
+    pub fn grid(&self, addr: &str) -> f32 {
+        return read_lock(&self.client).grid(addr);
+    }
+    pub fn encoder(&self, addr: &str) -> f32 {
+        return read_lock(&self.client).encoder(addr);
+    }
+    pub fn radar(&self, addr: &str) -> Vec2 {
+        return read_lock(&self.client).radar(addr);
+    }
+    pub fn polar(&self, addr: &str) -> Polar {
+        return read_lock(&self.client).polar(addr);
+    }
+    pub fn radial(&self, addr: &str) -> f32 {
+        return read_lock(&self.client).radial(addr);
+    }
+    pub fn radio(&self, addr: &str) -> i32 {
+        return read_lock(&self.client).radio(addr);
+    }
+    pub fn radio_enum<T: TouchOscRadioEnum>(&self, addr: &str) -> T {
+        return read_lock(&self.client).radio_enum(addr);
+    }
+    pub fn xy(&self, addr: &str) -> Vec2 {
+        return read_lock(&self.client).xy(addr);
+    }
+    pub fn text(&self, addr: &str) -> String {
+        return read_lock(&self.client).text(addr).to_string();
+    }
+
+    // set outputs
+
+    pub fn set_label(&self, addr: &str, text: &str) {
+        write_lock(&self.client).set_label(addr, text);
+    }
+    pub fn set_meter(&self, addr: &str, value: f32) {
+        write_lock(&self.client).set_meter(addr, value);
+    }
+    // restores the input's default, see TouchOscClient::reset
+    pub fn reset(&self, addr: &str) {
+        write_lock(&self.client).reset(addr);
+    }
+    // sends queued outputs, the receiver thread doesn't call update()
+    pub fn flush(&self) {
+        let mut client = write_lock(&self.client);
+        client.send_meters();
+        client.flush();
+    }
+}
+
+// a panic on another thread leaves the values usable, so poisoning is ignored
+
+fn read_lock(client: &RwLock<TouchOscClient>) -> RwLockReadGuard<'_, TouchOscClient> {
+    return client.read().unwrap_or_else(|err| err.into_inner());
+}
+pub(crate) fn write_lock(client: &RwLock<TouchOscClient>) -> RwLockWriteGuard<'_, TouchOscClient> {
+    return client.write().unwrap_or_else(|err| err.into_inner());
+}
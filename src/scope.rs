@@ -0,0 +1,260 @@
+// nannou_touchosc scopes
+// mikhail mansion
+// https://mikhailmansion.art
+
+// A view of a client under an address prefix. Addresses passed to a scope are
+// relative to it, so a sketch component can register and read its own inputs
+// without knowing where it is mounted in the layout:
+//
+//     fn register(touchosc: &mut TouchOscScope<&mut TouchOscClient>) {
+//         touchosc.add_fader("/speed", 0.0, 1.0, 0.5);
+//     }
+//
+//     register(&mut client.scope("/scene1")); // registers "/scene1/speed"
+//     register(&mut client.scope("/scene2")); // registers "/scene2/speed"
+//
+//     let speed = client.scope_ref("/scene1").fader("/speed");
+
+use crate::{
+    Polar, TouchOscClient, TouchOscCurve, TouchOscInputType, TouchOscRadioEnum, TouchOscValue, Vec2,
+};
+use std::ops::{Deref, DerefMut};
+use std::time::Instant;
+
+pub struct TouchOscScope<C> {
+    client: C,
+    prefix: String, //without trailing slash
+}
+
+impl TouchOscClient {
+    // scope for registering and reading inputs below prefix
+    pub fn scope(&mut self, prefix: &str) -> TouchOscScope<&mut TouchOscClient> {
+        return TouchOscScope::new(self, prefix);
+    }
+    // read-only scope, e.g. inside a nannou view function
+    pub fn scope_ref(&self, prefix: &str) -> TouchOscScope<&TouchOscClient> {
+        return TouchOscScope::new(self, prefix);
+    }
+}
+
+impl<C: Deref<Target = TouchOscClient>> TouchOscScope<C> {
+    fn new(client: C, prefix: &str) -> Self {
+        TouchOscScope {
+            client,
+            prefix: prefix.trim_end_matches('/').to_string(),
+        }
+    }
+    pub fn prefix(&self) -> &str {
+        return &self.prefix;
+    }
+    // absolute address of an address relative to the scope
+    pub fn addr(&self, addr: &str) -> String {
+        return format!("{}/{}", self.prefix, addr.trim_start_matches('/'));
+    }
+    // nested read-only scope, prefixes are joined
+    pub fn scope_ref(&self, prefix: &str) -> TouchOscScope<&TouchOscClient> {
+        return TouchOscScope::new(&self.client, &self.addr(prefix));
+    }
+    pub fn client(&self) -> &TouchOscClient {
+        return &self.client;
+    }
+
+    // get input values
+
+    pub fn button(&self, addr: &str) -> bool {
+        return self.client.button(&self.addr(addr));
+    }
+    pub fn fader(&self, addr: &str) -> f32 {
+        return self.client.fader(&self.addr(addr));
+    }
+    pub fn grid(&self, addr: &str) -> f32 {
+        return self.client.grid(&self.addr(addr));
+    }
+    pub fn encoder(&self, addr: &str) -> f32 {
+        return self.client.encoder(&self.addr(addr));
+    }
+    pub fn radar(&self, addr: &str) -> Vec2 {
+        return self.client.radar(&self.addr(addr));
+    }
+    pub fn polar(&self, addr: &str) -> Polar {
+        return self.client.polar(&self.addr(addr));
+    }
+    pub fn radial(&self, addr: &str) -> f32 {
+        return self.client.radial(&self.addr(addr));
+    }
+    pub fn radio(&self, addr: &str) -> i32 {
+        return self.client.radio(&self.addr(addr));
+    }
+    pub fn radio_enum<T: TouchOscRadioEnum>(&self, addr: &str) -> T {
+        return self.client.radio_enum(&self.addr(addr));
+    }
+    pub fn xy(&self, addr: &str) -> Vec2 {
+        return self.client.xy(&self.addr(addr));
+    }
+    pub fn raw(&self, addr: &str) -> Option<TouchOscValue> {
+        return self.client.raw(&self.addr(addr));
+    }
+    pub fn text(&self, addr: &str) -> &str {
+        return self.client.text(&self.addr(addr));
+    }
+    pub fn label(&self, addr: &str) -> &str {
+        return self.client.label(&self.addr(addr));
+    }
+    pub fn meter(&self, addr: &str) -> f32 {
+        return self.client.meter(&self.addr(addr));
+    }
+    pub fn value(&self, addr: &str) -> Option<TouchOscValue> {
+        return self.client.value(&self.addr(addr));
+    }
+
+    // change detection
+
+    pub fn changed(&self, addr: &str) -> bool {
+        return self.client.changed(&self.addr(addr));
+    }
+    pub fn changed_since(&self, addr: &str, generation: u64) -> bool {
+        return self.client.changed_since(&self.addr(addr), generation);
+    }
+    pub fn updated(&self, addr: &str) -> Option<Instant> {
+        return self.client.updated(&self.addr(addr));
+    }
+}
+
+impl<C: DerefMut<Target = TouchOscClient>> TouchOscScope<C> {
+    // nested scope, prefixes are joined
+    pub fn scope(&mut self, prefix: &str) -> TouchOscScope<&mut TouchOscClient> {
+        let prefix = self.addr(prefix);
+        return TouchOscScope::new(&mut *self.client, &prefix);
+    }
+    pub fn client_mut(&mut self) -> &mut TouchOscClient {
+        return &mut self.client;
+    }
+
+    // add inputs to client
+
+    pub fn add_button(&mut self, addr: &str, default: bool) {
+        let addr = self.addr(addr);
+        self.client.add_button(&addr, default);
+    }
+    pub fn add_fader(&mut self, addr: &str, min: f32, max: f32, default: f32) {
+        let addr = self.addr(addr);
+        self.client.add_fader(&addr, min, max, default);
+    }
+    pub fn add_grid(&mut self, addr: &str, size: usize, min: f32, max: f32, default: f32) {
+        let addr = self.addr(addr);
+        self.client.add_grid(&addr, size, min, max, default);
+    }
+    pub fn add_encoder(&mut self, addr: &str, min: f32, max: f32, default: f32) {
+        let addr = self.addr(addr);
+        self.client.add_encoder(&addr, min, max, default);
+    }
+    pub fn add_radar(&mut self, addr: &str, rad: (f32, f32, f32), rot: (f32, f32, f32)) {
+        let addr = self.addr(addr);
+        self.client.add_radar(&addr, rad, rot);
+    }
+    pub fn add_radial(&mut self, addr: &str, min: f32, max: f32, default: f32) {
+        let addr = self.addr(addr);
+        self.client.add_radial(&addr, min, max, default);
+    }
+    pub fn add_radio(&mut self, addr: &str, size: usize, default: i32) {
+        let addr = self.addr(addr);
+        self.client.add_radio(&addr, size, default);
+    }
+    pub fn add_radio_enum<T: TouchOscRadioEnum>(&mut self, addr: &str) {
+        let addr = self.addr(addr);
+        self.client.add_radio_enum::<T>(&addr);
+    }
+    pub fn add_xy(&mut self, addr: &str, min: f32, max: f32, default: f32) {
+        let addr = self.addr(addr);
+        self.client.add_xy(&addr, min, max, default);
+    }
+    pub fn add_xy_axes(&mut self, addr: &str, x: (f32, f32, f32), y: (f32, f32, f32)) {
+        let addr = self.addr(addr);
+        self.client.add_xy_axes(&addr, x, y);
+    }
+
+    pub fn add_text(&mut self, addr: &str, default: &str) {
+        let addr = self.addr(addr);
+        self.client.add_text(&addr, default);
+    }
+
+    // outputs
+
+    pub fn add_label(&mut self, addr: &str, text: &str) {
+        let addr = self.addr(addr);
+        self.client.add_label(&addr, text);
+    }
+    pub fn set_label(&mut self, addr: &str, text: &str) {
+        let addr = self.addr(addr);
+        self.client.set_label(&addr, text);
+    }
+    pub fn add_meter(&mut self, addr: &str, min: f32, max: f32) {
+        let addr = self.addr(addr);
+        self.client.add_meter(&addr, min, max);
+    }
+    pub fn set_meter(&mut self, addr: &str, value: f32) {
+        let addr = self.addr(addr);
+        self.client.set_meter(&addr, value);
+    }
+
+    // edit inputs
+
+    pub fn set_range(&mut self, addr: &str, min: f32, max: f32) {
+        let addr = self.addr(addr);
+        self.client.set_range(&addr, min, max);
+    }
+    pub fn set_radius_range(&mut self, addr: &str, min: f32, max: f32) {
+        let addr = self.addr(addr);
+        self.client.set_radius_range(&addr, min, max);
+    }
+    pub fn set_rotation_range(&mut self, addr: &str, min: f32, max: f32) {
+        let addr = self.addr(addr);
+        self.client.set_rotation_range(&addr, min, max);
+    }
+    pub fn set_xy_range(&mut self, addr: &str, x: (f32, f32), y: (f32, f32)) {
+        let addr = self.addr(addr);
+        self.client.set_xy_range(&addr, x, y);
+    }
+    pub fn set_curve(&mut self, addr: &str, curve: TouchOscCurve) {
+        let addr = self.addr(addr);
+        self.client.set_curve(&addr, curve);
+    }
+    pub fn set_default<V: Into<TouchOscValue>>(&mut self, addr: &str, default: V) {
+        let addr = self.addr(addr);
+        self.client.set_default(&addr, default);
+    }
+
+    // reset inputs
+
+    pub fn reset(&mut self, addr: &str) {
+        let addr = self.addr(addr);
+        self.client.reset(&addr);
+    }
+    pub fn reset_prefix(&mut self, prefix: &str) -> usize {
+        let prefix = self.addr(prefix);
+        return self.client.reset_prefix(&prefix);
+    }
+    // resets every input in the scope
+    pub fn reset_all(&mut self) -> usize {
+        return self.client.reset_prefix(&self.prefix);
+    }
+    pub fn reset_on_double_tap(&mut self, addr: &str) {
+        let addr = self.addr(addr);
+        self.client.reset_on_double_tap(&addr);
+    }
+
+    // remove inputs from client
+
+    pub fn remove(&mut self, addr: &str) -> Option<TouchOscInputType> {
+        let addr = self.addr(addr);
+        return self.client.remove(&addr);
+    }
+    pub fn remove_prefix(&mut self, prefix: &str) -> usize {
+        let prefix = self.addr(prefix);
+        return self.client.remove_prefix(&prefix);
+    }
+    // removes every input in the scope
+    pub fn clear(&mut self) -> usize {
+        return self.client.remove_prefix(&self.prefix);
+    }
+}
@@ -0,0 +1,114 @@
+// nannou_touchosc snapshots
+// mikhail mansion
+// https://mikhailmansion.art
+
+// Every input value at a single point in time. Reading several values from a
+// snapshot can't observe a message applied in between, which matters once a
+// receiver thread is running. Snapshots are immutable, Send and cheap to clone.
+
+use crate::{pt2, Polar, TouchOscAngle, TouchOscInputType, TouchOscRadioEnum, TouchOscValue, Vec2};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Default)]
+pub struct TouchOscSnapshot {
+    //grid elements are stored by element address
+    values: Arc<HashMap<String, (TouchOscInputType, TouchOscValue)>>,
+    angle_units: Arc<HashMap<String, TouchOscAngle>>, //per radar
+}
+
+impl TouchOscSnapshot {
+    pub(crate) fn new(
+        values: HashMap<String, (TouchOscInputType, TouchOscValue)>,
+        angle_units: HashMap<String, TouchOscAngle>,
+    ) -> Self {
+        TouchOscSnapshot {
+            values: Arc::new(values),
+            angle_units: Arc::new(angle_units),
+        }
+    }
+
+    // get input values
+
+    pub fn button(&self, addr: &str) -> bool {
+        return match self.get(addr) {
+            (TouchOscInputType::Button, TouchOscValue::Bool(state)) => state,
+            _ => false,
+        };
+    }
+    pub fn fader(&self, addr: &str) -> f32 {
+        return match self.get(addr) {
+            (TouchOscInputType::Fader, TouchOscValue::Float(value)) => value,
+            _ => 0.0,
+        };
+    }
+    pub fn grid(&self, addr: &str) -> f32 {
+        return match self.values.get(addr) {
+            Some((TouchOscInputType::Grid, TouchOscValue::Float(value))) => *value,
+            _ => 0.0,
+        };
+    }
+    pub fn encoder(&self, addr: &str) -> f32 {
+        return match self.get(addr) {
+            (TouchOscInputType::Encoder, TouchOscValue::Float(value)) => value,
+            _ => 0.0,
+        };
+    }
+    pub fn radar(&self, addr: &str) -> Vec2 {
+        return match self.get(addr) {
+            (TouchOscInputType::Radar, TouchOscValue::Vec2(values)) => values,
+            _ => pt2(0.0, 0.0),
+        };
+    }
+    // radar as (radius, angle in radians), see TouchOscClient::set_angle_unit
+    pub fn polar(&self, addr: &str) -> Polar {
+        let values = self.radar(addr);
+        let unit = self.angle_units.get(addr).copied().unwrap_or_default();
+        return Polar::new(values.x, unit.to_radians(values.y));
+    }
+    pub fn radial(&self, addr: &str) -> f32 {
+        return match self.get(addr) {
+            (TouchOscInputType::Radial, TouchOscValue::Float(value)) => value,
+            _ => 0.0,
+        };
+    }
+    pub fn radio(&self, addr: &str) -> i32 {
+        return match self.get(addr) {
+            (TouchOscInputType::Radio, TouchOscValue::Int(value)) => value,
+            _ => 0,
+        };
+    }
+    pub fn radio_enum<T: TouchOscRadioEnum>(&self, addr: &str) -> T {
+        let index = self.radio(addr) as usize;
+        match T::VARIANTS.get(index) {
+            Some(variant) => *variant,
+            None => T::VARIANTS[0],
+        }
+    }
+    pub fn xy(&self, addr: &str) -> Vec2 {
+        return match self.get(addr) {
+            (TouchOscInputType::XY, TouchOscValue::Vec2(values)) => values,
+            _ => pt2(0.0, 0.0),
+        };
+    }
+    pub fn text(&self, addr: &str) -> &str {
+        return match self.values.get(addr) {
+            Some((TouchOscInputType::Text, TouchOscValue::Text(value))) => value,
+            Some(_) => "",
+            None => panic!("\"{}\" is not an address!", addr),
+        };
+    }
+    pub fn value(&self, addr: &str) -> Option<TouchOscValue> {
+        return self.values.get(addr).map(|(_, value)| value.clone());
+    }
+
+    // helpers
+
+    // panics like the client does for unregistered addresses
+    fn get(&self, addr: &str) -> (TouchOscInputType, TouchOscValue) {
+        match self.values.get(addr) {
+            Some(entry) => entry.clone(),
+            None => panic!("\"{}\" is not an address!", addr),
+        }
+    }
+}
@@ -0,0 +1,121 @@
+// nannou_touchosc async stream
+// mikhail mansion
+// https://mikhailmansion.art
+
+// Receives on a non-blocking tokio socket and yields one event per input that
+// received a message. Messages go through the same registry, coercion and
+// range mapping as `TouchOscClient::update`.
+//
+//     let mut touchosc = TouchOscClient::unbound();
+//     touchosc.add_fader("/my-fader", 0.0, 1.0, 0.5);
+//     let mut stream = touchosc.into_stream(6555).await?;
+//     while let Some(event) = stream.next().await {
+//         println!("{} {:?}", event.addr, event.value);
+//     }
+
+use crate::{osc, TouchOscClient, TouchOscValue};
+use futures_core::Stream;
+use std::collections::VecDeque;
+use std::io;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::io::ReadBuf;
+use tokio::net::{ToSocketAddrs, UdpSocket};
+use tracing::warn;
+
+// large enough for any UDP datagram
+const BUFFER_SIZE: usize = 65_535;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TouchOscEvent {
+    pub addr: String, // registered address (grid element address for grids)
+    pub value: TouchOscValue,
+    pub from: SocketAddr,
+}
+
+pub struct TouchOscStream {
+    client: TouchOscClient,
+    socket: UdpSocket,
+    buffer: Vec<u8>,
+    events: VecDeque<TouchOscEvent>,
+}
+
+impl TouchOscClient {
+    // binds a tokio socket to port, any blocking receiver of the client is dropped first
+    pub async fn into_stream(mut self, port: u16) -> io::Result<TouchOscStream> {
+        self.osc_receiver = None;
+        let socket = UdpSocket::bind(("0.0.0.0", port)).await?;
+        Ok(TouchOscStream {
+            client: self,
+            socket,
+            buffer: vec![0; BUFFER_SIZE],
+            events: VecDeque::new(),
+        })
+    }
+}
+
+impl TouchOscStream {
+    // the client holding the current values
+    pub fn client(&self) -> &TouchOscClient {
+        return &self.client;
+    }
+    pub fn client_mut(&mut self) -> &mut TouchOscClient {
+        return &mut self.client;
+    }
+    // sends a message through the stream's socket, e.g. feedback to the device
+    pub async fn send<A: ToSocketAddrs>(
+        &self,
+        target: A,
+        addr: &str,
+        args: Vec<osc::Type>,
+    ) -> io::Result<usize> {
+        let packet = osc::Packet::Message(osc::Message {
+            addr: addr.to_string(),
+            args: Some(args),
+        });
+        let bytes = osc::encode(packet)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, format!("{:?}", err)))?;
+        self.socket.send_to(&bytes, target).await
+    }
+}
+
+impl Stream for TouchOscStream {
+    type Item = TouchOscEvent;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<TouchOscEvent>> {
+        let stream = self.get_mut();
+        loop {
+            if let Some(event) = stream.events.pop_front() {
+                return Poll::Ready(Some(event));
+            }
+
+            let mut buffer = ReadBuf::new(&mut stream.buffer);
+            let from = match stream.socket.poll_recv_from(cx, &mut buffer) {
+                Poll::Ready(Ok(from)) => from,
+                //transient: a connection reset after an ICMP port unreachable on Windows
+                Poll::Ready(Err(err)) if err.kind() == io::ErrorKind::ConnectionReset => {
+                    warn!(target: "nannou_touchosc::dispatch", error = %err, "receive failed");
+                    continue;
+                }
+                //anything else won't go away by polling again, end the stream
+                Poll::Ready(Err(err)) => {
+                    warn!(target: "nannou_touchosc::dispatch", error = %err, "receive failed, stream ended");
+                    return Poll::Ready(None);
+                }
+                Poll::Pending => return Poll::Pending,
+            };
+            //undecodable datagrams are skipped
+            let packet = match osc::decode(buffer.filled()) {
+                Ok(packet) => packet,
+                Err(_) => continue,
+            };
+            stream.client.generation += 1;
+            for addr in stream.client.apply_packet(packet, from) {
+                if let Some(value) = stream.client.value(&addr) {
+                    stream.events.push_back(TouchOscEvent { addr, value, from });
+                }
+            }
+        }
+    }
+}
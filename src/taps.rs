@@ -0,0 +1,115 @@
+// nannou_touchosc double-tap reset
+// mikhail mansion
+// https://mikhailmansion.art
+
+// Resets an input to its default when it is touched twice in quick succession.
+// Taps are read from the touch messages TouchOSC sends next to the value, at
+// the input's address followed by "/touch" (or "/z" for TouchOSC Mk1), so they
+// have to be enabled in the control's OSC settings.
+//
+//     touchosc.add_fader("/volume", 0.0, 1.0, 0.8);
+//     touchosc.reset_on_double_tap("/volume");
+
+use crate::{float_arg, in_namespace, osc, TouchOscClient, TouchOscInputType};
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+pub const DEFAULT_DOUBLE_TAP_INTERVAL: Duration = Duration::from_millis(300);
+
+pub(crate) struct TouchOscTaps {
+    enabled: HashSet<String>, //inputs, a grid enables all of its elements
+    last_down: HashMap<String, Instant>, //input or grid element -> last touch down
+    held: HashSet<String>,    //reset and still touched
+    interval: Duration,
+}
+
+impl Default for TouchOscTaps {
+    fn default() -> Self {
+        TouchOscTaps {
+            enabled: HashSet::new(),
+            last_down: HashMap::new(),
+            held: HashSet::new(),
+            interval: DEFAULT_DOUBLE_TAP_INTERVAL,
+        }
+    }
+}
+
+impl TouchOscTaps {
+    pub(crate) fn remove(&mut self, addr: &str) {
+        self.enabled.remove(addr);
+        self.last_down.retain(|key, _| !in_namespace(key, addr));
+        self.held.retain(|key| !in_namespace(key, addr));
+    }
+    pub(crate) fn clear(&mut self) {
+        self.enabled.clear();
+        self.last_down.clear();
+        self.held.clear();
+    }
+    // values sent while a reset input is still touched would undo the reset
+    pub(crate) fn holds(&self, addr: &str) -> bool {
+        return self.held.contains(addr)
+            || addr
+                .rsplit_once('/')
+                .is_some_and(|(base, _)| self.held.contains(base));
+    }
+}
+
+impl TouchOscClient {
+    // resets the input at addr (or every element of a grid) when it is double tapped
+    pub fn reset_on_double_tap(&mut self, addr: &str) {
+        self.verify_has_addr(addr);
+        self.taps.enabled.insert(addr.to_string());
+    }
+    pub fn set_double_tap_interval(&mut self, interval: Duration) {
+        self.taps.interval = interval;
+    }
+    pub fn resets_on_double_tap(&self, addr: &str) -> bool {
+        return self.taps.enabled.contains(addr);
+    }
+
+    // input or grid element touched by a "/touch" or "/z" message at addr,
+    // with whether it is touched down; None for other messages
+    pub(crate) fn touch_of(&self, addr: &str, args: &[osc::Type]) -> Option<(String, bool)> {
+        if self.taps.enabled.is_empty() || self.lookup_table.contains_key(addr) {
+            return None;
+        }
+        let (target, suffix) = addr.rsplit_once('/')?;
+        if suffix != "touch" && suffix != "z" {
+            return None;
+        }
+        let enabled = self.taps.enabled.contains(target)
+            || match target.rsplit_once('/') {
+                Some((grid, _)) => {
+                    self.taps.enabled.contains(grid)
+                        && self.lookup_table.get(grid) == Some(&TouchOscInputType::Grid)
+                }
+                None => false,
+            };
+        if !enabled {
+            return None;
+        }
+        let down = float_arg(args, false)? > 0.0;
+        return Some((target.to_string(), down));
+    }
+    // returns target if the touch completed a double tap and reset it
+    pub(crate) fn apply_touch(&mut self, target: &str, down: bool) -> Option<String> {
+        if !down {
+            self.taps.held.remove(target);
+            return None;
+        }
+        let now = Instant::now();
+        return match self.taps.last_down.insert(target.to_string(), now) {
+            Some(last) if now.duration_since(last) <= self.taps.interval => {
+                self.taps.last_down.remove(target);
+                self.taps.held.insert(target.to_string());
+                if self.verbose {
+                    debug!(target: "nannou_touchosc::dispatch", addr = target, "double tap reset");
+                }
+                self.reset(target);
+                Some(target.to_string())
+            }
+            _ => None,
+        };
+    }
+}
@@ -0,0 +1,649 @@
+// nannou_touchosc .tosc layout parsing
+// mikhail mansion
+// https://mikhailmansion.art
+
+// TouchOSC (mk2) layouts are saved as zlib compressed xml documents.
+// Only the parts needed to map controls onto OSC addresses are read here:
+// the node type, its "name" property, the first enabled OSC send path
+// (receive path for labels and meters), radio steps and grid children.
+
+use crate::{TouchOscInputType, TouchOscOutputType};
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use roxmltree::{Document, Node};
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+// size of generated layouts and of each control cell
+const LAYOUT_W: f32 = 640.0;
+const CELL_MARGIN: f32 = 10.0;
+
+#[derive(Debug, Clone)]
+pub struct TouchOscLayoutControl {
+    pub name: String,
+    pub node_type: String, // as written in the layout, e.g. "FADER"
+    pub input_type: Option<TouchOscInputType>,
+    pub output_type: Option<TouchOscOutputType>, // controls the sketch writes to
+    pub addr: String,   // resolved OSC path (grid: base path without the index)
+    pub size: usize,    // radio steps, grid child count, otherwise 1
+    pub default_x: f32, // layout default of the "x" value (0..1, or radio index)
+    pub default_y: f32, // layout default of the "y" value (xy/radar)
+}
+
+#[derive(Debug, Clone)]
+pub struct TouchOscLayout {
+    controls: Vec<TouchOscLayoutControl>,
+}
+
+impl TouchOscLayout {
+    pub fn new(controls: Vec<TouchOscLayoutControl>) -> Self {
+        TouchOscLayout { controls }
+    }
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let bytes = fs::read(path)?;
+        Self::parse(&bytes)
+    }
+    pub fn parse(bytes: &[u8]) -> io::Result<Self> {
+        let mut xml = String::new();
+        ZlibDecoder::new(bytes).read_to_string(&mut xml)?;
+        Self::from_xml(&xml)
+    }
+    pub fn from_xml(xml: &str) -> io::Result<Self> {
+        let doc = Document::parse(xml).map_err(invalid_data)?;
+        let mut controls = Vec::new();
+        for node in doc.root_element().children().filter(is_node) {
+            collect(node, &mut controls);
+        }
+        Ok(TouchOscLayout { controls })
+    }
+    pub fn controls(&self) -> &[TouchOscLayoutControl] {
+        return &self.controls;
+    }
+    pub fn control(&self, addr: &str) -> Option<&TouchOscLayoutControl> {
+        return self.controls.iter().find(|control| control.addr == addr);
+    }
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(self.to_xml().as_bytes())?;
+        fs::write(path, encoder.finish()?)
+    }
+    // writes every control into a single root group, laid out on a grid
+    pub fn to_xml(&self) -> String {
+        let controls: Vec<&TouchOscLayoutControl> = self
+            .controls
+            .iter()
+            .filter(|control| control.input_type.is_some() || control.output_type.is_some())
+            .collect();
+        let cols = (controls.len() as f32).sqrt().ceil().max(1.0) as usize;
+        let rows = controls.len().div_ceil(cols);
+        let cell = LAYOUT_W / cols as f32;
+
+        let mut ids = 0;
+        let mut children = String::new();
+        for (i, control) in controls.iter().enumerate() {
+            let frame = (
+                (i % cols) as f32 * cell + CELL_MARGIN,
+                (i / cols) as f32 * cell + CELL_MARGIN,
+                cell - CELL_MARGIN * 2.0,
+                cell - CELL_MARGIN * 2.0,
+            );
+            children.push_str(&write_control(control, frame, &mut ids));
+        }
+
+        let frame = (0.0, 0.0, LAYOUT_W, rows.max(1) as f32 * cell);
+        let mut xml = String::from("<?xml version='1.0' encoding='UTF-8'?><lexml version='3'>");
+        xml.push_str(&write_node(
+            "GROUP",
+            &common_properties("root", frame, 0, false),
+            "",
+            "",
+            &children,
+            &mut ids,
+        ));
+        xml.push_str("</lexml>");
+        xml
+    }
+}
+
+// helpers
+
+pub(crate) fn invalid_data<E: std::fmt::Display>(err: E) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err.to_string())
+}
+
+pub fn input_type_from_node(node_type: &str) -> Option<TouchOscInputType> {
+    match node_type {
+        "BUTTON" => Some(TouchOscInputType::Button),
+        "FADER" => Some(TouchOscInputType::Fader),
+        "GRID" => Some(TouchOscInputType::Grid),
+        "ENCODER" => Some(TouchOscInputType::Encoder),
+        "RADAR" => Some(TouchOscInputType::Radar),
+        "RADIAL" => Some(TouchOscInputType::Radial),
+        "RADIO" => Some(TouchOscInputType::Radio),
+        "XY" => Some(TouchOscInputType::XY),
+        "TEXT" => Some(TouchOscInputType::Text),
+        _ => None,
+    }
+}
+
+// a fader the performer can't move is a meter
+pub fn output_type_from_node(node_type: &str, interactive: bool) -> Option<TouchOscOutputType> {
+    match node_type {
+        "LABEL" => Some(TouchOscOutputType::Label),
+        "FADER" if !interactive => Some(TouchOscOutputType::Meter),
+        _ => None,
+    }
+}
+
+fn collect(node: Node, controls: &mut Vec<TouchOscLayoutControl>) {
+    let node_type = node.attribute("type").unwrap_or("").to_string();
+    let children: Vec<Node> = match child(node, "children") {
+        Some(children) => children.children().filter(is_node).collect(),
+        None => Vec::new(),
+    };
+
+    let interactive = property(node, "interactive").as_deref() != Some("0");
+    let output_type = output_type_from_node(&node_type, interactive);
+
+    let addr = if node_type == "GRID" {
+        //grid elements send to "<base>/<index>", strip the index from the first child
+        children
+            .first()
+            .and_then(|first| osc_path(*first, false))
+            .and_then(|path| path.rsplit_once('/').map(|(base, _)| base.to_string()))
+    } else {
+        osc_path(node, output_type.is_some())
+    };
+
+    if let Some(addr) = addr {
+        let size = match node_type.as_str() {
+            "GRID" => children.len(),
+            "RADIO" => property(node, "steps")
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(1),
+            _ => 1,
+        };
+        //grid values live on the elements, the grid node has none
+        let value_node = match node_type.as_str() {
+            "GRID" => children.first().copied().unwrap_or(node),
+            _ => node,
+        };
+        controls.push(TouchOscLayoutControl {
+            name: name(node),
+            input_type: match output_type {
+                Some(_) => None,
+                None => input_type_from_node(&node_type),
+            },
+            output_type,
+            node_type: node_type.clone(),
+            addr,
+            size,
+            default_x: default_value(value_node, "x"),
+            default_y: default_value(value_node, "y"),
+        });
+    }
+
+    if node_type != "GRID" {
+        for c in children {
+            collect(c, controls);
+        }
+    }
+}
+
+// receive: also accept paths the control only receives on (labels, meters)
+fn osc_path(node: Node, receive: bool) -> Option<String> {
+    let messages = child(node, "messages")?;
+    let osc = messages
+        .children()
+        .filter(|n| n.has_tag_name("osc"))
+        .find(|osc| {
+            flag(*osc, "enabled") && (flag(*osc, "send") || receive && flag(*osc, "receive"))
+        })?;
+    let mut path = String::new();
+    for partial in child(osc, "path")?
+        .children()
+        .filter(|n| n.has_tag_name("partial"))
+    {
+        let value = child(partial, "value").and_then(|v| v.text()).unwrap_or("");
+        match child(partial, "type").and_then(|t| t.text()) {
+            Some("CONSTANT") => path.push_str(value),
+            Some("PROPERTY") => match value {
+                "name" => path.push_str(&name(node)),
+                "parent.name" => path.push_str(&parent_node(node).map(name).unwrap_or_default()),
+                _ => (),
+            },
+            Some("INDEX") => {
+                //the child index is offset by scaleMin (grid elements usually start at 1)
+                let offset = child(partial, "scaleMin")
+                    .and_then(|v| v.text())
+                    .and_then(|v| v.parse::<usize>().ok())
+                    .unwrap_or(0);
+                path.push_str(&(index(node) + offset).to_string())
+            }
+            _ => (),
+        }
+    }
+    if path.is_empty() {
+        return None;
+    }
+    Some(path)
+}
+
+fn is_node(node: &Node) -> bool {
+    node.has_tag_name("node")
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, tag: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|c| c.has_tag_name(tag))
+}
+
+fn flag(node: Node, tag: &str) -> bool {
+    child(node, tag).and_then(|n| n.text()) == Some("1")
+}
+
+fn parent_node<'a, 'input>(node: Node<'a, 'input>) -> Option<Node<'a, 'input>> {
+    // <node> -> <children> -> <node>
+    node.parent().and_then(|children| children.parent())
+}
+
+fn index(node: Node) -> usize {
+    match node.parent() {
+        Some(children) => children
+            .children()
+            .filter(is_node)
+            .take_while(|n| *n != node)
+            .count(),
+        None => 0,
+    }
+}
+
+fn property(node: Node, key: &str) -> Option<String> {
+    child(node, "properties")?
+        .children()
+        .filter(|n| n.has_tag_name("property"))
+        .find(|p| child(*p, "key").and_then(|k| k.text()) == Some(key))
+        .and_then(|p| child(p, "value"))
+        .and_then(|v| v.text())
+        .map(|text| text.to_string())
+}
+
+fn name(node: Node) -> String {
+    property(node, "name").unwrap_or_default()
+}
+
+fn default_value(node: Node, key: &str) -> f32 {
+    let text = child(node, "values").and_then(|values| {
+        values
+            .children()
+            .filter(|n| n.has_tag_name("value"))
+            .find(|v| child(*v, "key").and_then(|k| k.text()) == Some(key))
+            .and_then(|v| child(v, "default"))
+            .and_then(|d| d.text())
+    });
+    match text {
+        Some("true") => 1.0,
+        Some(text) => text.parse().unwrap_or(0.0),
+        None => 0.0,
+    }
+}
+
+// layout writing
+
+type Frame = (f32, f32, f32, f32);
+
+fn write_control(control: &TouchOscLayoutControl, frame: Frame, ids: &mut usize) -> String {
+    let name = control.addr.rsplit('/').next().unwrap_or("");
+    let input_type = match (control.input_type, control.output_type) {
+        (Some(input_type), _) => input_type,
+        (None, Some(output_type)) => return write_output(control, output_type, frame, ids),
+        (None, None) => return String::new(),
+    };
+    let xy = matches!(input_type, TouchOscInputType::Radar | TouchOscInputType::XY);
+    let conversion = match input_type {
+        TouchOscInputType::Radio => "INTEGER",
+        TouchOscInputType::Text => "STRING",
+        _ => "FLOAT",
+    };
+
+    let mut properties = common_properties(name, frame, 0, true);
+    let mut values = value_xml("touch", "false");
+    values.push_str(&value_xml("x", &control.default_x.to_string()));
+    if xy {
+        values.push_str(&value_xml("y", &control.default_y.to_string()));
+    }
+    let mut children = String::new();
+    let vars: &[&str] = if xy { &["x", "y"] } else { &["x"] };
+    let mut messages = osc_xml(
+        &[cdata_partial("CONSTANT", &control.addr)],
+        conversion,
+        vars,
+        true,
+    );
+
+    match input_type {
+        TouchOscInputType::Button => {
+            properties.push_str(&property_xml("i", "buttonType", "2"));
+        }
+        TouchOscInputType::Text => {
+            values = value_xml("text", "");
+            messages = osc_xml(
+                &[cdata_partial("CONSTANT", &control.addr)],
+                conversion,
+                &["text"],
+                true,
+            );
+        }
+        TouchOscInputType::Radio => {
+            properties.push_str(&property_xml("i", "steps", &control.size.to_string()));
+            properties.push_str(&property_xml("i", "radioType", "0"));
+        }
+        TouchOscInputType::Grid => {
+            //grid elements are horizontal faders stacked vertically, sending "<addr>/<1..n>"
+            properties = common_properties(name, frame, 1, true);
+            properties.push_str(&property_xml("i", "gridX", "1"));
+            properties.push_str(&property_xml("i", "gridY", &control.size.to_string()));
+            properties.push_str(&property_xml("i", "gridType", "4"));
+            properties.push_str(&property_xml("b", "exclusive", "0"));
+            values = value_xml("touch", "false");
+            messages = String::new();
+
+            let size = control.size.max(1);
+            let h = frame.3 / size as f32;
+            for i in 0..size {
+                let element_frame = (0.0, i as f32 * h, frame.2, h);
+                let path = [
+                    cdata_partial("CONSTANT", &format!("{}/", control.addr)),
+                    format!(
+                        "<partial><type>INDEX</type><conversion>INTEGER</conversion><value></value><scaleMin>1</scaleMin><scaleMax>{}</scaleMax></partial>",
+                        size
+                    ),
+                ];
+                let mut element_values = value_xml("touch", "false");
+                element_values.push_str(&value_xml("x", &control.default_x.to_string()));
+                children.push_str(&write_node(
+                    "FADER",
+                    &common_properties(&(i + 1).to_string(), element_frame, 1, true),
+                    &element_values,
+                    &osc_xml(&path, "FLOAT", &["x"], true),
+                    "",
+                    ids,
+                ));
+            }
+        }
+        _ => (),
+    }
+
+    let node_type = match input_type {
+        TouchOscInputType::Button => "BUTTON",
+        TouchOscInputType::Fader => "FADER",
+        TouchOscInputType::Grid => "GRID",
+        TouchOscInputType::Encoder => "ENCODER",
+        TouchOscInputType::Radar => "RADAR",
+        TouchOscInputType::Radial => "RADIAL",
+        TouchOscInputType::Radio => "RADIO",
+        TouchOscInputType::XY => "XY",
+        TouchOscInputType::Text => "TEXT",
+    };
+    write_node(node_type, &properties, &values, &messages, &children, ids)
+}
+
+fn write_output(
+    control: &TouchOscLayoutControl,
+    output_type: TouchOscOutputType,
+    frame: Frame,
+    ids: &mut usize,
+) -> String {
+    let name = control.addr.rsplit('/').next().unwrap_or("");
+    let path = [cdata_partial("CONSTANT", &control.addr)];
+    match output_type {
+        TouchOscOutputType::Label => write_node(
+            "LABEL",
+            &common_properties(name, frame, 0, false),
+            &value_xml("text", ""),
+            &osc_xml(&path, "STRING", &["text"], false),
+            "",
+            ids,
+        ),
+        TouchOscOutputType::Meter => {
+            let mut values = value_xml("touch", "false");
+            values.push_str(&value_xml("x", &control.default_x.to_string()));
+            write_node(
+                "FADER",
+                &common_properties(name, frame, 0, false),
+                &values,
+                &osc_xml(&path, "FLOAT", &["x"], false),
+                "",
+                ids,
+            )
+        }
+    }
+}
+
+fn write_node(
+    node_type: &str,
+    properties: &str,
+    values: &str,
+    messages: &str,
+    children: &str,
+    ids: &mut usize,
+) -> String {
+    *ids += 1;
+    let mut xml = format!(
+        "<node ID='00000000-0000-4000-8000-{:012x}' type='{}'><properties>{}</properties><values>{}</values>",
+        ids, node_type, properties, values
+    );
+    if !messages.is_empty() {
+        xml.push_str(&format!("<messages>{}</messages>", messages));
+    }
+    if !children.is_empty() {
+        xml.push_str(&format!("<children>{}</children>", children));
+    }
+    xml.push_str("</node>");
+    xml
+}
+
+fn common_properties(
+    name: &str,
+    (x, y, w, h): Frame,
+    orientation: i32,
+    interactive: bool,
+) -> String {
+    let mut xml = String::new();
+    xml.push_str(&property_xml("b", "background", "1"));
+    xml.push_str("<property type='c'><key><![CDATA[color]]></key><value><r>0</r><g>1</g><b>1</b><a>1</a></value></property>");
+    xml.push_str(&format!(
+        "<property type='r'><key><![CDATA[frame]]></key><value><x>{}</x><y>{}</y><w>{}</w><h>{}</h></value></property>",
+        x, y, w, h
+    ));
+    xml.push_str(&property_xml(
+        "b",
+        "interactive",
+        if interactive { "1" } else { "0" },
+    ));
+    xml.push_str(&property_xml("b", "locked", "0"));
+    xml.push_str(&property_xml("s", "name", name));
+    xml.push_str(&property_xml("i", "orientation", &orientation.to_string()));
+    xml.push_str(&property_xml("b", "outline", "1"));
+    xml.push_str(&property_xml("i", "shape", "1"));
+    xml.push_str(&property_xml("b", "visible", "1"));
+    xml
+}
+
+fn property_xml(kind: &str, key: &str, value: &str) -> String {
+    let value = match kind {
+        "s" => cdata(value),
+        _ => value.to_string(),
+    };
+    format!(
+        "<property type='{}'><key>{}</key><value>{}</value></property>",
+        kind,
+        cdata(key),
+        value
+    )
+}
+
+fn value_xml(key: &str, default: &str) -> String {
+    format!(
+        "<value><key>{}</key><locked>0</locked><lockedDefaultCurrent>0</lockedDefaultCurrent><default>{}</default><defaultPull>0</defaultPull></value>",
+        cdata(key),
+        cdata(default)
+    )
+}
+
+// vars are the sent values, e.g. ["x", "y"] for an xy pad,
+// send is off for outputs, which only receive
+fn osc_xml(path: &[String], conversion: &str, vars: &[&str], send: bool) -> String {
+    let triggers: String = vars
+        .iter()
+        .map(|var| {
+            format!(
+                "<trigger><var>{}</var><condition>ANY</condition></trigger>",
+                cdata(var)
+            )
+        })
+        .collect();
+    let arguments: String = vars
+        .iter()
+        .map(|var| {
+            format!(
+                "<partial><type>VALUE</type><conversion>{}</conversion><value>{}</value><scaleMin>0</scaleMin><scaleMax>1</scaleMax></partial>",
+                conversion,
+                cdata(var)
+            )
+        })
+        .collect();
+    format!(
+        "<osc><enabled>1</enabled><send>{}</send><receive>1</receive><feedback>0</feedback><connections>00001</connections><triggers>{}</triggers><path>{}</path><arguments>{}</arguments></osc>",
+        if send { 1 } else { 0 },
+        triggers,
+        path.concat(),
+        arguments
+    )
+}
+
+fn cdata_partial(partial_type: &str, value: &str) -> String {
+    format!(
+        "<partial><type>{}</type><conversion>STRING</conversion><value>{}</value><scaleMin>0</scaleMin><scaleMax>1</scaleMax></partial>",
+        partial_type,
+        cdata(value)
+    )
+}
+
+fn cdata(text: &str) -> String {
+    format!("<![CDATA[{}]]>", text.replace("]]>", "]]]]><![CDATA[>"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::send;
+    use crate::validate::TouchOscValidation;
+    use crate::{osc, TouchOscClient};
+
+    fn client() -> TouchOscClient {
+        let mut client = TouchOscClient::unbound();
+        client.add_button("/button", true);
+        client.add_fader("/fader", 0.0, 10.0, 2.5);
+        client.add_grid("/grid", 3, 0.0, 1.0, 0.5);
+        client.add_radio("/radio", 4, 2);
+        client.add_xy_axes("/xy", (0.0, 1.0, 0.25), (0.0, 2.0, 1.5));
+        client.add_radar("/radar", (0.0, 1.0, 0.5), (0.0, 1.0, 0.75));
+        client.add_text("/text", "");
+        client.add_label("/label", "level");
+        client.add_meter("/meter", 0.0, 10.0);
+        client
+    }
+
+    #[test]
+    fn export_parse_round_trip() {
+        //positions moved mid-session don't end up in the layout
+        let mut client = client();
+        send(&mut client, "/fader", vec![osc::Type::Float(0.9)]);
+        send(&mut client, "/grid/1", vec![osc::Type::Float(0.9)]);
+        send(&mut client, "/radio", vec![osc::Type::Int(3)]);
+        send(
+            &mut client,
+            "/xy",
+            vec![osc::Type::Float(0.9), osc::Type::Float(0.9)],
+        );
+        send(&mut client, "/button", vec![osc::Type::Float(0.0)]);
+        client.set_meter("/meter", 2.5);
+        let exported = client.layout();
+        let parsed = TouchOscLayout::from_xml(&exported.to_xml()).unwrap();
+        assert_eq!(parsed.controls().len(), exported.controls().len());
+        for control in exported.controls() {
+            let round_trip = parsed.control(&control.addr).unwrap();
+            assert_eq!(
+                round_trip.input_type, control.input_type,
+                "{}",
+                control.addr
+            );
+            assert_eq!(
+                round_trip.output_type, control.output_type,
+                "{}",
+                control.addr
+            );
+            assert_eq!(round_trip.size, control.size, "{}", control.addr);
+            assert_eq!(round_trip.default_x, control.default_x, "{}", control.addr);
+            assert_eq!(round_trip.default_y, control.default_y, "{}", control.addr);
+        }
+
+        let grid = parsed.control("/grid").unwrap();
+        assert_eq!((grid.size, grid.default_x), (3, 0.5));
+        let radio = parsed.control("/radio").unwrap();
+        assert_eq!((radio.size, radio.default_x), (4, 2.0));
+        let xy = parsed.control("/xy").unwrap();
+        assert_eq!((xy.default_x, xy.default_y), (0.25, 0.75));
+        assert_eq!(parsed.control("/fader").unwrap().default_x, 0.25);
+        assert_eq!(parsed.control("/button").unwrap().default_x, 1.0);
+        let meter = parsed.control("/meter").unwrap();
+        assert_eq!(meter.node_type, "FADER");
+        assert_eq!(meter.input_type, None);
+        assert_eq!(meter.default_x, 0.0);
+    }
+
+    #[test]
+    fn export_save_open_validate() {
+        let path = std::env::temp_dir().join(format!(
+            "nannou_touchosc_round_trip_{}.tosc",
+            std::process::id()
+        ));
+        let client = client();
+        client.export_tosc(&path).unwrap();
+        let report = client.validate_against_tosc(&path);
+        let _ = fs::remove_file(&path);
+        let report = report.unwrap();
+        assert!(report.is_ok(), "{}", report);
+    }
+
+    #[test]
+    fn validate_reports_mismatches() {
+        let layout = client().layout();
+        let mut other = TouchOscClient::unbound();
+        other.add_fader("/button", 0.0, 1.0, 0.0);
+        other.add_grid("/grid", 2, 0.0, 1.0, 0.0);
+        other.add_radio("/radio", 5, 0);
+        other.add_fader("/missing", 0.0, 1.0, 0.0);
+        other.add_label("/status", "");
+        let report = TouchOscValidation::compare(&other.layout(), &layout);
+
+        //the layout's label and meter are not reported, they need no input
+        assert_eq!(report.missing_in_layout, vec!["/missing", "/status"]);
+        assert_eq!(
+            report.unregistered,
+            vec!["/fader", "/radar", "/text", "/xy"]
+        );
+        assert_eq!(report.type_mismatches.len(), 1);
+        assert_eq!(report.type_mismatches[0].addr, "/button");
+        assert_eq!(report.type_mismatches[0].layout, "BUTTON");
+        let mut sizes: Vec<(&str, usize, usize)> = report
+            .size_mismatches
+            .iter()
+            .map(|m| (m.addr.as_str(), m.registered, m.layout))
+            .collect();
+        sizes.sort();
+        assert_eq!(sizes, vec![("/grid", 2, 3), ("/radio", 5, 4)]);
+    }
+}
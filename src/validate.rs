@@ -0,0 +1,130 @@
+// nannou_touchosc layout validation
+// mikhail mansion
+// https://mikhailmansion.art
+
+// Compares the controls registered on a client (or any layout) against a
+// .tosc layout, so a mismatched controller is caught before a show rather
+// than during it.
+
+use crate::tosc::TouchOscLayout;
+use crate::TouchOscInputType;
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TouchOscTypeMismatch {
+    pub addr: String,
+    pub registered: TouchOscInputType,
+    pub layout: String, // node type in the layout, e.g. "FADER"
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TouchOscSizeMismatch {
+    pub addr: String,
+    pub registered: usize,
+    pub layout: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TouchOscValidation {
+    pub missing_in_layout: Vec<String>, // registered, but no layout control sends here
+    pub unregistered: Vec<String>,      // layout control sends here, but nothing is registered
+    pub type_mismatches: Vec<TouchOscTypeMismatch>,
+    pub size_mismatches: Vec<TouchOscSizeMismatch>,
+}
+
+impl TouchOscValidation {
+    // `expected` holds the registered controls, `layout` the controller
+    pub fn compare(expected: &TouchOscLayout, layout: &TouchOscLayout) -> Self {
+        let mut report = TouchOscValidation::default();
+        let layout_controls: HashMap<&str, _> = layout
+            .controls()
+            .iter()
+            .map(|control| (control.addr.as_str(), control))
+            .collect();
+
+        for registered in expected.controls() {
+            let registered_type = match registered.input_type {
+                Some(input_type) => input_type,
+                None => {
+                    //outputs only need a control to display them, of any type
+                    if registered.output_type.is_some()
+                        && !layout_controls.contains_key(registered.addr.as_str())
+                    {
+                        report.missing_in_layout.push(registered.addr.clone());
+                    }
+                    continue;
+                }
+            };
+            match layout_controls.get(registered.addr.as_str()) {
+                None => report.missing_in_layout.push(registered.addr.clone()),
+                Some(control) if control.input_type != Some(registered_type) => {
+                    report.type_mismatches.push(TouchOscTypeMismatch {
+                        addr: registered.addr.clone(),
+                        registered: registered_type,
+                        layout: control.node_type.clone(),
+                    })
+                }
+                Some(control) => match registered_type {
+                    TouchOscInputType::Grid | TouchOscInputType::Radio
+                        if control.size != registered.size =>
+                    {
+                        report.size_mismatches.push(TouchOscSizeMismatch {
+                            addr: registered.addr.clone(),
+                            registered: registered.size,
+                            layout: control.size,
+                        })
+                    }
+                    _ => (),
+                },
+            }
+        }
+
+        for control in layout.controls() {
+            //labels in the layout don't have to be driven by the sketch
+            if control.output_type.is_none() && expected.control(&control.addr).is_none() {
+                report.unregistered.push(control.addr.clone());
+            }
+        }
+
+        report.missing_in_layout.sort();
+        report.unregistered.sort();
+        report.unregistered.dedup();
+        report
+    }
+    pub fn is_ok(&self) -> bool {
+        return self.missing_in_layout.is_empty()
+            && self.unregistered.is_empty()
+            && self.type_mismatches.is_empty()
+            && self.size_mismatches.is_empty();
+    }
+}
+
+impl fmt::Display for TouchOscValidation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_ok() {
+            return writeln!(f, "layout matches registered controls");
+        }
+        for addr in &self.missing_in_layout {
+            writeln!(f, "missing in layout: {}", addr)?;
+        }
+        for addr in &self.unregistered {
+            writeln!(f, "not registered: {}", addr)?;
+        }
+        for mismatch in &self.type_mismatches {
+            writeln!(
+                f,
+                "type mismatch: {} registered as {:?}, layout has {}",
+                mismatch.addr, mismatch.registered, mismatch.layout
+            )?;
+        }
+        for mismatch in &self.size_mismatches {
+            writeln!(
+                f,
+                "size mismatch: {} registered with {}, layout has {}",
+                mismatch.addr, mismatch.registered, mismatch.layout
+            )?;
+        }
+        Ok(())
+    }
+}